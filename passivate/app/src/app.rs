@@ -1,6 +1,7 @@
 use eframe::Frame;
 use egui::Context;
 use passivate_configuration::configuration_source::FileConfigurationSource;
+use passivate_coverage::coverage_status::CoverageStatus;
 use passivate_egui_docking::docking_layout::DockingLayout;
 use passivate_egui_docking::layout_management::LayoutManagement;
 use passivate_log::log_message::LogMessage;
@@ -18,6 +19,7 @@ pub struct App<'a>
     run_hyps_tx: tokio::sync::mpsc::Sender<HypRunRequest<RustBridge>>,
     source_change_rx: crossbeam_channel::Receiver<SourceChangeEvent>,
     session_event_rx: crossbeam_channel::Receiver<HypSessionEvent<RustBridge>>,
+    coverage_rx: crossbeam_channel::Receiver<CoverageStatus>,
     log_rx: crossbeam_channel::Receiver<LogMessage>
 }
 
@@ -29,6 +31,7 @@ impl<'a> App<'a>
         run_hyps_tx: tokio::sync::mpsc::Sender<HypRunRequest<RustBridge>>,
         source_change_rx: crossbeam_channel::Receiver<SourceChangeEvent>,
         session_event_rx: crossbeam_channel::Receiver<HypSessionEvent<RustBridge>>,
+        coverage_rx: crossbeam_channel::Receiver<CoverageStatus>,
         log_rx: crossbeam_channel::Receiver<LogMessage>
     ) -> Self
     {
@@ -38,6 +41,7 @@ impl<'a> App<'a>
             run_hyps_tx,
             source_change_rx,
             session_event_rx,
+            coverage_rx,
             log_rx
         }
     }
@@ -50,6 +54,7 @@ impl<'a> App<'a>
             &self.run_hyps_tx,
             &self.source_change_rx,
             &self.session_event_rx,
+            &self.coverage_rx,
             &self.log_rx
         );
     }
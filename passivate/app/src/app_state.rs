@@ -1,6 +1,11 @@
+use camino::Utf8PathBuf;
+#[cfg(feature = "testing")]
+use passivate_configuration::configuration::PassivateConfiguration;
 use passivate_configuration::configuration_manager::ConfigurationManager;
+use passivate_configuration::run_selection::RunSelection;
 use passivate_core::passivate_state::PassivateState;
 use passivate_core::passivate_state_change::PassivateStateChange;
+use passivate_coverage::coverage_status::CoverageStatus;
 use passivate_delegation::tx_rx::Rx;
 use passivate_egui_core::passivate_view_state::PassivateViewState;
 use passivate_egui_docking::dock_views::DockViews;
@@ -11,6 +16,7 @@ use passivate_log::log_message::LogMessage;
 use passivate_model_bridge::bridge::Bridge;
 use passivate_model_bridge::hyp_run_bridge::RunHypsBridge;
 use passivate_model_bridge::hyp_session_event::HypSessionEvent;
+use passivate_model_bridge::hyp_state::HypState;
 use passivate_model_bridge::source_change_event::SourceChangeEvent;
 use passivate_model_core::hyp_session::HypSession;
 use passivate_model_core::hyp_session_change::HypSessionChange;
@@ -22,7 +28,42 @@ pub struct AppState<TBridge: Bridge>
     view_state: PassivateViewState<TBridge>,
     dock_views: DockViews<PassivateView>,
     configuration: ConfigurationManager,
-    first_update: bool
+    first_update: bool,
+    queued_hyp: Option<QueuedHyp<TBridge::Id>>
+}
+
+/// A hyp requested to run along with another run, which would be cancelled by it, so it waits for that run to end
+struct QueuedHyp<TId>
+{
+    hyp_id: TId,
+    /// Runs that have to end first, which includes a run still going when the hyp was queued
+    runs_to_end: usize,
+    running: bool
+}
+
+impl<TId> QueuedHyp<TId>
+{
+    fn new(hyp_id: TId, running: bool) -> Self
+    {
+        Self {
+            hyp_id,
+            runs_to_end: 1 + usize::from(running),
+            running
+        }
+    }
+
+    /// Follow whether the session is running, which gives back the hyp once the runs it waits for ended
+    fn update(mut self, running: bool) -> Result<TId, Self>
+    {
+        if self.running && !running
+        {
+            self.runs_to_end -= 1;
+        }
+
+        self.running = running;
+
+        if self.runs_to_end == 0 { Ok(self.hyp_id) } else { Err(self) }
+    }
 }
 
 impl<TBridge: Bridge> AppState<TBridge>
@@ -41,10 +82,12 @@ impl<TBridge: Bridge> AppState<TBridge>
             view_state,
             dock_views,
             configuration,
-            first_update: true
+            first_update: true,
+            queued_hyp: None
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update_app(
         &mut self,
         egui_context: &egui::Context,
@@ -52,27 +95,44 @@ impl<TBridge: Bridge> AppState<TBridge>
         run_hyps: &impl RunHypsBridge<TBridge>,
         source_change_rx: &impl Rx<SourceChangeEvent>,
         session_event_rx: &impl Rx<HypSessionEvent<TBridge>>,
+        coverage_rx: &impl Rx<CoverageStatus>,
         log_rx: &impl Rx<LogMessage>
     )
     {
-        let mut rerun_required = self.first_update;
+        let mut rerun_all_required = self.first_update;
         self.first_update = false;
 
-        if source_change_rx.try_recv().is_ok()
+        let mut changed_files = Vec::new();
+
+        while let Ok(SourceChangeEvent::File(changed_file)) = source_change_rx.try_recv()
         {
-            rerun_required = true;
+            changed_files.push(changed_file);
         }
 
         let session_change = self.session.update_next(session_event_rx).and_then(map_session_change);
 
         {
             self.state.update_state(session_change.as_ref());
+            self.state.update_coverage(coverage_rx);
 
             let configuration = &*self.configuration.acquire();
             self.view_state
                 .update_view_state(session_change.as_ref(), configuration, egui_context, log_rx);
         }
 
+        let running = self.session.state() == HypState::Running;
+
+        let mut hyp_to_run = match self.queued_hyp.take().map(|queued_hyp| queued_hyp.update(running))
+        {
+            Some(Ok(hyp_id)) => Some(hyp_id),
+            Some(Err(queued_hyp)) =>
+            {
+                self.queued_hyp = Some(queued_hyp);
+                None
+            }
+            None => None
+        };
+
         let ui_changes = {
             let configuration = &*self.configuration.acquire();
 
@@ -89,7 +149,7 @@ impl<TBridge: Bridge> AppState<TBridge>
 
         for ui_change in ui_changes
         {
-            rerun_required |= ui_change.requires_rerun();
+            rerun_all_required |= ui_change.requires_rerun();
 
             match ui_change
             {
@@ -97,6 +157,7 @@ impl<TBridge: Bridge> AppState<TBridge>
                 {
                     _ = self.configuration.change(configuration_change);
                 }
+                PassivateStateChange::RunHyp(hyp) => hyp_to_run = Some(hyp.id().clone()),
                 ui_change =>
                 {
                     self.state.update_state(Some(&ui_change));
@@ -108,15 +169,64 @@ impl<TBridge: Bridge> AppState<TBridge>
             }
         }
 
-        if rerun_required
+        if !rerun_all_required && changed_files.is_empty()
         {
-            let configuration = &*self.configuration.acquire();
+            if let Some(hyp_id) = hyp_to_run
+            {
+                run_hyps.run_single(
+                    hyp_id,
+                    self.configuration.acquire().clone(),
+                    self.configuration.paths().clone()
+                );
+            }
+
+            return;
+        }
+
+        // The run requested below would cancel the hyp's run, so the hyp runs once that run ended
+        if let Some(hyp_id) = hyp_to_run.or_else(|| self.queued_hyp.take().map(|queued_hyp| queued_hyp.hyp_id))
+        {
+            self.queued_hyp = Some(QueuedHyp::new(hyp_id, running));
+        }
+
+        let configuration = &*self.configuration.acquire();
+        let paths = self.configuration.paths().clone();
 
-            run_hyps.run_all(configuration.clone(), self.configuration.paths().clone());
+        let changed_files: Option<Vec<Utf8PathBuf>> = changed_files
+            .into_iter()
+            .map(|changed_file| Utf8PathBuf::from_path_buf(changed_file).ok())
+            .collect();
+
+        match (changed_files, configuration.run_selection)
+        {
+            // Coverage describes the whole workspace, so it always needs every hyp to run
+            (Some(changed_files), RunSelection::AffectedPackages) if !rerun_all_required && !configuration.coverage_enabled =>
+            {
+                run_hyps.run_affected(changed_files, configuration.clone(), paths)
+            }
+            (Some(changed_files), RunSelection::CoveredLines) if !rerun_all_required =>
+            {
+                run_hyps.run_impacted(changed_files, configuration.clone(), paths)
+            }
+            _ if configuration.failed_hyps_first =>
+            {
+                run_hyps.run_failed_first(failed_hyps(&self.session), configuration.clone(), paths)
+            }
+            _ => run_hyps.run_all(configuration.clone(), paths)
         }
     }
 }
 
+fn failed_hyps<TBridge: Bridge>(session: &HypSession<TBridge>) -> Vec<TBridge::Id>
+{
+    session
+        .hyps()
+        .iter()
+        .filter(|hyp| matches!(hyp.state(), HypState::Failed | HypState::TimedOut))
+        .map(|hyp| hyp.id().clone())
+        .collect()
+}
+
 fn map_session_change<TBridge: Bridge>(change: HypSessionChange<TBridge>) -> Option<PassivateStateChange<TBridge>>
 {
     match change
@@ -133,12 +243,13 @@ impl<TBridge: Bridge> AppState<TBridge>
     #[builder]
     pub fn stub(
         #[builder(default = HypSession::new())] session: HypSession<TBridge>,
-        #[builder(default = true)] first_update: bool
+        #[builder(default = true)] first_update: bool,
+        #[builder(default = PassivateConfiguration::default())] configuration: PassivateConfiguration
     ) -> (AppState<TBridge>, DockingLayout)
     {
         let state = PassivateState::new();
         let view_state = PassivateViewState::default();
-        let configuration = ConfigurationManager::stub().call();
+        let configuration = ConfigurationManager::stub().configuration(configuration).call();
 
         let views = PassivateViews::stub();
 
@@ -150,7 +261,8 @@ impl<TBridge: Bridge> AppState<TBridge>
             view_state,
             dock_views,
             configuration,
-            first_update
+            first_update,
+            queued_hyp: None
         };
 
         (app_state, layout)
@@ -163,13 +275,19 @@ pub mod tests
 {
     use std::path::PathBuf;
 
+    use camino::{Utf8Path, Utf8PathBuf};
     use egui::accesskit::Role;
     use egui_kittest::Harness;
     use egui_kittest::kittest::{Key, Queryable};
     use itertools::Itertools;
     use maybe_owned::MaybeOwned;
+    use mockall::Sequence;
     use mockall::predicate::{always, eq};
     use passivate_configuration::configuration::PassivateConfiguration;
+    use passivate_configuration::ignored_hyps::IgnoredHyps;
+    use passivate_configuration::run_selection::RunSelection;
+    use passivate_coverage::coverage_status::CoverageStatus;
+    use passivate_coverage::grcov::covdir_json::CovdirJson;
     use passivate_egui_docking::view::View;
     use passivate_egui_views::passivate_views::PassivateViews;
     use passivate_hyp_names::hyp_id::HypId;
@@ -179,9 +297,10 @@ pub mod tests
     use passivate_model_bridge::hyp_run_bridge::MockRunHypsBridge;
     use passivate_model_bridge::hyp_session_bridge::{CompleteRunBridge, SendHypBridge, StartRunBridge};
     use passivate_model_bridge::hyp_state::HypState;
+    use passivate_model_bridge::run_extent::RunExtent;
     use passivate_model_bridge::source_change_event::SourceChangeEvent;
     use passivate_run_rust::model::{RustBridge, RustHyp};
-    use passivate_testing::model::{TestHyp, TestHypKind, TestSession};
+    use passivate_testing::model::{TestHyp, TestHypKind, TestId, TestSession};
 
     use crate::app_state::AppState;
     use crate::testing::app_state::UpdateApp;
@@ -190,7 +309,7 @@ pub mod tests
     pub fn selecting_a_test_shows_it_in_details_view()
     {
         let mut session = TestSession::new();
-        session.start_run();
+        session.start_run(RunExtent::AllHyps);
         session.send_hyp(HypReport::new_fixed(
             TestHypKind::Hyp(TestHyp::new("example_test")),
             HypState::Passed
@@ -231,7 +350,7 @@ pub mod tests
         let hyp_info = example_hyp();
         let hyp_report = HypReport::new_fixed(hyp_info, HypState::Passed);
 
-        session_tx.start_run();
+        session_tx.start_run(RunExtent::AllHyps);
         ui.step();
         session_tx.send_hyp(hyp_report);
         ui.step();
@@ -284,6 +403,163 @@ pub mod tests
         ui.step();
     }
 
+    #[test]
+    pub fn when_affected_packages_are_selected_hyps_affected_by_source_change_are_run()
+    {
+        let configuration = PassivateConfiguration {
+            run_selection: RunSelection::AffectedPackages,
+            ..Default::default()
+        };
+
+        let (mut app_state, mut layout) = AppState::<RustBridge>::stub()
+            .first_update(false)
+            .configuration(configuration)
+            .call();
+
+        let mut mock_run_hyps = MockRunHypsBridge::new();
+        mock_run_hyps
+            .expect_run_affected()
+            .once()
+            .with(eq(vec![Utf8PathBuf::from("project/src/lib.rs")]), always(), always())
+            .return_const(());
+        mock_run_hyps.expect_run_all().never();
+
+        let (source_change_tx, source_change_rx) = crossbeam_channel::unbounded();
+
+        source_change_tx
+            .send(SourceChangeEvent::File(PathBuf::from("project/src/lib.rs")))
+            .unwrap();
+
+        let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+            UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                .with_run_hyps(MaybeOwned::Borrowed(&mock_run_hyps))
+                .with_source_change_rx(MaybeOwned::Borrowed(&source_change_rx))
+                .call();
+        });
+
+        ui.step();
+    }
+
+    #[test]
+    pub fn when_affected_packages_are_selected_with_coverage_enabled_all_hyps_are_run()
+    {
+        let configuration = PassivateConfiguration {
+            run_selection: RunSelection::AffectedPackages,
+            coverage_enabled: true,
+            ..Default::default()
+        };
+
+        let (mut app_state, mut layout) = AppState::<RustBridge>::stub()
+            .first_update(false)
+            .configuration(configuration)
+            .call();
+
+        let mut mock_run_hyps = MockRunHypsBridge::new();
+        mock_run_hyps.expect_run_all().once().return_const(());
+        mock_run_hyps.expect_run_affected().never();
+
+        let (source_change_tx, source_change_rx) = crossbeam_channel::unbounded();
+
+        source_change_tx
+            .send(SourceChangeEvent::File(PathBuf::from("project/src/lib.rs")))
+            .unwrap();
+
+        let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+            UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                .with_run_hyps(MaybeOwned::Borrowed(&mock_run_hyps))
+                .with_source_change_rx(MaybeOwned::Borrowed(&source_change_rx))
+                .call();
+        });
+
+        ui.step();
+    }
+
+    #[test]
+    pub fn when_covered_lines_are_selected_hyps_impacted_by_source_change_are_run()
+    {
+        let configuration = PassivateConfiguration {
+            run_selection: RunSelection::CoveredLines,
+            coverage_enabled: true,
+            ..Default::default()
+        };
+
+        let (mut app_state, mut layout) = AppState::<RustBridge>::stub()
+            .first_update(false)
+            .configuration(configuration)
+            .call();
+
+        let mut mock_run_hyps = MockRunHypsBridge::new();
+        mock_run_hyps
+            .expect_run_impacted()
+            .once()
+            .with(eq(vec![Utf8PathBuf::from("project/src/lib.rs")]), always(), always())
+            .return_const(());
+        mock_run_hyps.expect_run_all().never();
+
+        let (source_change_tx, source_change_rx) = crossbeam_channel::unbounded();
+
+        source_change_tx
+            .send(SourceChangeEvent::File(PathBuf::from("project/src/lib.rs")))
+            .unwrap();
+
+        let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+            UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                .with_run_hyps(MaybeOwned::Borrowed(&mock_run_hyps))
+                .with_source_change_rx(MaybeOwned::Borrowed(&source_change_rx))
+                .call();
+        });
+
+        ui.step();
+    }
+
+    #[test]
+    pub fn when_failed_hyps_first_is_configured_hyps_that_failed_are_run_first()
+    {
+        let mut session = TestSession::new();
+        session.start_run(RunExtent::AllHyps);
+        session.send_hyp(HypReport::new_fixed(
+            TestHypKind::Hyp(TestHyp::new("failing_test")),
+            HypState::Failed
+        ));
+        session.send_hyp(HypReport::new_fixed(
+            TestHypKind::Hyp(TestHyp::new("passing_test")),
+            HypState::Passed
+        ));
+        session.complete_run();
+
+        let configuration = PassivateConfiguration {
+            failed_hyps_first: true,
+            ..Default::default()
+        };
+
+        let (mut app_state, mut layout) = AppState::stub()
+            .session(session.into())
+            .first_update(false)
+            .configuration(configuration)
+            .call();
+
+        let mut mock_run_hyps = MockRunHypsBridge::new();
+        mock_run_hyps
+            .expect_run_failed_first()
+            .once()
+            .with(eq(vec![TestId::from("failing_test")]), always(), always())
+            .return_const(());
+        mock_run_hyps.expect_run_all().never();
+
+        let (source_change_tx, source_change_rx) = crossbeam_channel::unbounded();
+
+        source_change_tx.send(SourceChangeEvent::File(PathBuf::default())).unwrap();
+
+        let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+            UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                .with_run_hyps(MaybeOwned::Borrowed(&mock_run_hyps))
+                .with_source_change_rx(MaybeOwned::Borrowed(&source_change_rx))
+                .call();
+        });
+
+        ui.step();
+    }
+
     #[test]
     pub fn when_configuration_view_enables_coverage_hyps_run_with_coverage_enabled()
     {
@@ -314,6 +590,125 @@ pub mod tests
         ui.run();
     }
 
+    #[test]
+    pub fn when_configuration_view_includes_ignored_hyps_hyps_run_including_them()
+    {
+        let (mut app_state, mut layout) = AppState::<RustBridge>::stub().first_update(false).call();
+        let mut mock_run_hyps = MockRunHypsBridge::new();
+        mock_run_hyps.expect_run_all().once().with(
+            eq(PassivateConfiguration {
+                ignored_hyps: IgnoredHyps::Include,
+                ..Default::default()
+            }),
+            always()
+        );
+        mock_run_hyps.expect_run_single().never();
+
+        let views = PassivateViews::stub();
+        let configuration_tab = layout.dock_state().find_tab(&views.configuration_dock().id()).unwrap();
+        layout.dock_state().set_active_tab(configuration_tab);
+
+        let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+            UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                .with_run_hyps(MaybeOwned::Borrowed(&mock_run_hyps))
+                .call();
+        });
+
+        let include_ignored = ui.get_by_label("Include");
+        include_ignored.click();
+
+        ui.run();
+    }
+
+    #[test]
+    pub fn running_a_skipped_hyp_from_details_view_runs_it_on_its_own()
+    {
+        let mut session = TestSession::new();
+        session.start_run(RunExtent::AllHyps);
+        session.send_hyp(HypReport::new_fixed(
+            TestHypKind::Hyp(TestHyp::new("ignored_test")),
+            HypState::Skipped
+        ));
+        session.complete_run();
+
+        let (mut app_state, mut layout) = AppState::stub().session(session.into()).first_update(false).call();
+
+        let mut mock_run_hyps = MockRunHypsBridge::new();
+        mock_run_hyps
+            .expect_run_single()
+            .once()
+            .with(eq(TestId::from("ignored_test")), always(), always())
+            .return_const(());
+        mock_run_hyps.expect_run_all().never();
+
+        let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+            UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                .with_run_hyps(MaybeOwned::Borrowed(&mock_run_hyps))
+                .call();
+        });
+
+        ui.step();
+        ui.get_by_label("ignored_test").click();
+        ui.step();
+        ui.step();
+
+        ui.get_by_label("Run").click();
+        ui.step();
+    }
+
+    #[test]
+    pub fn a_hyp_run_along_with_a_source_change_runs_once_the_run_of_the_change_ended()
+    {
+        let mut session = TestSession::new();
+        session.start_run(RunExtent::AllHyps);
+        session.send_hyp(HypReport::new_fixed(
+            TestHypKind::Hyp(TestHyp::new("ignored_test")),
+            HypState::Skipped
+        ));
+        session.complete_run();
+
+        let (mut app_state, mut layout) = AppState::stub().session(session.into()).first_update(false).call();
+
+        let mut sequence = Sequence::new();
+        let mut mock_run_hyps = MockRunHypsBridge::new();
+        mock_run_hyps
+            .expect_run_all()
+            .once()
+            .in_sequence(&mut sequence)
+            .return_const(());
+        mock_run_hyps
+            .expect_run_single()
+            .once()
+            .with(eq(TestId::from("ignored_test")), always(), always())
+            .in_sequence(&mut sequence)
+            .return_const(());
+
+        let (source_change_tx, source_change_rx) = crossbeam_channel::unbounded();
+        let (mut session_tx, session_rx) = crossbeam_channel::unbounded();
+
+        let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+            UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                .with_run_hyps(MaybeOwned::Borrowed(&mock_run_hyps))
+                .with_source_change_rx(MaybeOwned::Borrowed(&source_change_rx))
+                .with_session_rx(MaybeOwned::Borrowed(&session_rx))
+                .call();
+        });
+
+        ui.step();
+        ui.get_by_label("ignored_test").click();
+        ui.step();
+        ui.step();
+
+        source_change_tx.send(SourceChangeEvent::File(PathBuf::default())).unwrap();
+        ui.get_by_label("Run").click();
+        ui.step();
+
+        session_tx.start_run(RunExtent::AllHyps);
+        ui.step();
+        session_tx.complete_run();
+        ui.step();
+    }
+
     #[test]
     pub fn enabling_coverage_in_coverage_view_modifies_configuration()
     {
@@ -360,6 +755,63 @@ pub mod tests
         assert_eq!(1, app_state.view_state.logs().len());
     }
 
+    #[test]
+    pub fn coverage_is_stored_in_state()
+    {
+        let (mut app_state, mut layout) = AppState::<RustBridge>::stub().call();
+        let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
+        coverage_tx.send(CoverageStatus::Preparing).unwrap();
+        coverage_tx.send(CoverageStatus::Running).unwrap();
+
+        {
+            let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+                UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                    .with_coverage_rx(MaybeOwned::Borrowed(&coverage_rx))
+                    .call();
+            });
+
+            ui.run();
+        }
+
+        assert_eq!(CoverageStatus::Running, app_state.state.coverage);
+    }
+
+    #[test]
+    pub fn coverage_diff_is_computed_between_completed_coverage_runs()
+    {
+        let (mut app_state, mut layout) = AppState::<RustBridge>::stub().call();
+        let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
+        let previous = CovdirJson {
+            coverage_percent: 80.0,
+            ..Default::default()
+        };
+
+        let current = CovdirJson {
+            coverage_percent: 60.0,
+            ..Default::default()
+        };
+
+        coverage_tx.send(CoverageStatus::Done(Box::new(previous))).unwrap();
+        coverage_tx.send(CoverageStatus::Running).unwrap();
+        coverage_tx.send(CoverageStatus::Done(Box::new(current))).unwrap();
+
+        {
+            let mut ui = Harness::new_ui(|ui: &mut egui::Ui| {
+                UpdateApp::with(&mut app_state, ui.ctx(), &mut layout)
+                    .with_coverage_rx(MaybeOwned::Borrowed(&coverage_rx))
+                    .call();
+            });
+
+            ui.run();
+        }
+
+        let diff = app_state.state.coverage_diff.unwrap();
+
+        assert_eq!(-20.0, diff.get(Utf8Path::new("")).unwrap().percent_delta);
+    }
+
     #[test]
     pub fn configuring_snapshots_path_starts_a_hyp_run()
     {
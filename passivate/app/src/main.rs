@@ -3,10 +3,13 @@ use passivate::start;
 use passivate_core::compose::compose;
 use passivate_core::passivate_args::PassivateArgs;
 use passivate_core::startup_errors::StartupError;
-use passivate_run_rust::hyp_run_handler;
+use passivate_run_rust::{hyp_run_handler, profile_runner};
 
 fn main() -> Result<(), StartupError>
 {
+    // Nextest starts test processes through passivate itself when coverage is enabled
+    profile_runner::run_if_requested();
+
     let args = PassivateArgs::parse();
 
     let runtime = hyp_run_handler::build_tokio_runtime();
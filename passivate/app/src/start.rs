@@ -6,6 +6,7 @@ use passivate_egui_docking::dock_views::DockViews;
 use passivate_egui_view_configuration::ConfigurationView;
 use passivate_egui_view_coverage::CoverageView;
 use passivate_egui_view_details::details_view::DetailsView;
+use passivate_egui_view_diagnostics::DiagnosticsView;
 use passivate_egui_view_log::LogView;
 use passivate_egui_view_session::session_view::SessionView;
 use passivate_egui_views::passivate_layout;
@@ -30,19 +31,28 @@ pub fn run_app_and_get_context(
         source_change_rx,
         hyp_run_tx,
         session_event_rx,
+        coverage_rx,
         configuration,
         log_rx,
         ..
     } = passivate;
 
     // Views
-    let tests_view = SessionView;
+    let tests_view = SessionView::new();
     let details_view = DetailsView;
-    let coverage_view = CoverageView;
+    let coverage_view = CoverageView::new(configuration.paths().root.clone());
     let configuration_view = ConfigurationView::new();
     let log_view = LogView;
+    let diagnostics_view = DiagnosticsView;
 
-    let views = PassivateViews::new(tests_view, details_view, coverage_view, configuration_view, log_view);
+    let views = PassivateViews::new(
+        tests_view,
+        details_view,
+        coverage_view,
+        configuration_view,
+        log_view,
+        diagnostics_view
+    );
 
     let layout_path = configuration.paths().passivate.join("default_docking_layout.toml");
     let layout = passivate_layout::load(&layout_path, &views).map_err(ConfigurationError::Load)?;
@@ -82,6 +92,7 @@ pub fn run_app_and_get_context(
                 hyp_run_tx,
                 source_change_rx,
                 session_event_rx,
+                coverage_rx,
                 log_rx
             )))
         })
@@ -1,4 +1,5 @@
 use maybe_owned::MaybeOwned;
+use passivate_coverage::coverage_status::CoverageStatus;
 use passivate_delegation::tx_rx::{MockRx, Rx, RxError};
 use passivate_egui_docking::docking_layout::DockingLayout;
 use passivate_log::log_message::LogMessage;
@@ -9,7 +10,7 @@ use passivate_model_bridge::source_change_event::SourceChangeEvent;
 
 use crate::app_state::AppState;
 
-pub struct UpdateApp<'a, TBridge: Bridge, TRunHyps, TRxSourceChange, TRxSession, TRxLog>
+pub struct UpdateApp<'a, TBridge: Bridge, TRunHyps, TRxSourceChange, TRxSession, TRxCoverage, TRxLog>
 {
     test_self: &'a mut AppState<TBridge>,
     egui_context: &'a egui::Context,
@@ -17,6 +18,7 @@ pub struct UpdateApp<'a, TBridge: Bridge, TRunHyps, TRxSourceChange, TRxSession,
     run_hyps: MaybeOwned<'a, TRunHyps>,
     source_change_rx: MaybeOwned<'a, TRxSourceChange>,
     session_rx: MaybeOwned<'a, TRxSession>,
+    coverage_rx: MaybeOwned<'a, TRxCoverage>,
     log_rx: MaybeOwned<'a, TRxLog>
 }
 
@@ -27,6 +29,7 @@ impl<'a, TBridge: Bridge>
         MockRunHypsBridge<TBridge>,
         MockRx<SourceChangeEvent>,
         MockRx<HypSessionEvent<TBridge>>,
+        MockRx<CoverageStatus>,
         MockRx<LogMessage>
     >
 {
@@ -35,6 +38,9 @@ impl<'a, TBridge: Bridge>
         let mut mock_run_hyps = MockRunHypsBridge::new();
         mock_run_hyps.expect_run_all().returning(|_, _| ());
         mock_run_hyps.expect_run_single().returning(|_, _, _| ());
+        mock_run_hyps.expect_run_affected().returning(|_, _, _| ());
+        mock_run_hyps.expect_run_impacted().returning(|_, _, _| ());
+        mock_run_hyps.expect_run_failed_first().returning(|_, _, _| ());
 
         let mut mock_source_change_rx = MockRx::new();
         mock_source_change_rx
@@ -52,6 +58,14 @@ impl<'a, TBridge: Bridge>
             .expect_try_recv()
             .returning(|| Err(RxError::TryRecv(crossbeam_channel::TryRecvError::Empty)));
 
+        let mut mock_coverage_rx = MockRx::new();
+        mock_coverage_rx
+            .expect_recv()
+            .returning(|| Err(RxError::Recv(crossbeam_channel::RecvError)));
+        mock_coverage_rx
+            .expect_try_recv()
+            .returning(|| Err(RxError::TryRecv(crossbeam_channel::TryRecvError::Empty)));
+
         let mut mock_log_rx = MockRx::new();
         mock_log_rx
             .expect_recv()
@@ -67,18 +81,19 @@ impl<'a, TBridge: Bridge>
             run_hyps: MaybeOwned::Owned(mock_run_hyps),
             source_change_rx: MaybeOwned::Owned(mock_source_change_rx),
             session_rx: MaybeOwned::Owned(mock_session_rx),
+            coverage_rx: MaybeOwned::Owned(mock_coverage_rx),
             log_rx: MaybeOwned::Owned(mock_log_rx)
         }
     }
 }
 
-impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
-    UpdateApp<'a, TBridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
+impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxCoverage, _TRxLog>
+    UpdateApp<'a, TBridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxCoverage, _TRxLog>
 {
     pub fn with_run_hyps<TRunHyps>(
         self,
         run_hyps: MaybeOwned<'a, TRunHyps>
-    ) -> UpdateApp<'a, TBridge, TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
+    ) -> UpdateApp<'a, TBridge, TRunHyps, _TRxSourceChange, _TRxSession, _TRxCoverage, _TRxLog>
     {
         UpdateApp {
             test_self: self.test_self,
@@ -87,6 +102,7 @@ impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
             run_hyps,
             source_change_rx: self.source_change_rx,
             session_rx: self.session_rx,
+            coverage_rx: self.coverage_rx,
             log_rx: self.log_rx
         }
     }
@@ -94,7 +110,7 @@ impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
     pub fn with_source_change_rx<TRxSourceChange>(
         self,
         source_change_rx: MaybeOwned<'a, TRxSourceChange>
-    ) -> UpdateApp<'a, TBridge, _TRunHyps, TRxSourceChange, _TRxSession, _TRxLog>
+    ) -> UpdateApp<'a, TBridge, _TRunHyps, TRxSourceChange, _TRxSession, _TRxCoverage, _TRxLog>
     {
         UpdateApp {
             test_self: self.test_self,
@@ -103,6 +119,7 @@ impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
             run_hyps: self.run_hyps,
             source_change_rx,
             session_rx: self.session_rx,
+            coverage_rx: self.coverage_rx,
             log_rx: self.log_rx
         }
     }
@@ -110,7 +127,7 @@ impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
     pub fn with_session_rx<TRxSession>(
         self,
         session_rx: MaybeOwned<'a, TRxSession>
-    ) -> UpdateApp<'a, TBridge, _TRunHyps, _TRxSourceChange, TRxSession, _TRxLog>
+    ) -> UpdateApp<'a, TBridge, _TRunHyps, _TRxSourceChange, TRxSession, _TRxCoverage, _TRxLog>
     {
         UpdateApp {
             test_self: self.test_self,
@@ -119,6 +136,24 @@ impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
             run_hyps: self.run_hyps,
             source_change_rx: self.source_change_rx,
             session_rx,
+            coverage_rx: self.coverage_rx,
+            log_rx: self.log_rx
+        }
+    }
+
+    pub fn with_coverage_rx<TRxCoverage>(
+        self,
+        coverage_rx: MaybeOwned<'a, TRxCoverage>
+    ) -> UpdateApp<'a, TBridge, _TRunHyps, _TRxSourceChange, _TRxSession, TRxCoverage, _TRxLog>
+    {
+        UpdateApp {
+            test_self: self.test_self,
+            egui_context: self.egui_context,
+            layout: self.layout,
+            run_hyps: self.run_hyps,
+            source_change_rx: self.source_change_rx,
+            session_rx: self.session_rx,
+            coverage_rx,
             log_rx: self.log_rx
         }
     }
@@ -126,7 +161,7 @@ impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
     pub fn with_log_rx<TRxLog>(
         self,
         log_rx: MaybeOwned<'a, TRxLog>
-    ) -> UpdateApp<'a, TBridge, _TRunHyps, _TRxSourceChange, _TRxSession, TRxLog>
+    ) -> UpdateApp<'a, TBridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxCoverage, TRxLog>
     {
         UpdateApp {
             test_self: self.test_self,
@@ -135,17 +170,19 @@ impl<'a, TBridge: Bridge, _TRunHyps, _TRxSourceChange, _TRxSession, _TRxLog>
             run_hyps: self.run_hyps,
             source_change_rx: self.source_change_rx,
             session_rx: self.session_rx,
+            coverage_rx: self.coverage_rx,
             log_rx
         }
     }
 }
 
-impl<'a, TBridge: Bridge, TRunHyps, TRxSourceChange, TRxSession, TRxLog>
-    UpdateApp<'a, TBridge, TRunHyps, TRxSourceChange, TRxSession, TRxLog>
+impl<'a, TBridge: Bridge, TRunHyps, TRxSourceChange, TRxSession, TRxCoverage, TRxLog>
+    UpdateApp<'a, TBridge, TRunHyps, TRxSourceChange, TRxSession, TRxCoverage, TRxLog>
 where
     TRunHyps: RunHypsBridge<TBridge>,
     TRxSourceChange: Rx<SourceChangeEvent>,
     TRxSession: Rx<HypSessionEvent<TBridge>>,
+    TRxCoverage: Rx<CoverageStatus>,
     TRxLog: Rx<LogMessage>
 {
     pub fn call(&mut self)
@@ -156,6 +193,7 @@ where
             self.run_hyps.as_ref(),
             self.source_change_rx.as_ref(),
             self.session_rx.as_ref(),
+            self.coverage_rx.as_ref(),
             self.log_rx.as_ref()
         );
     }
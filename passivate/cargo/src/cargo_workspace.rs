@@ -4,7 +4,22 @@ use cargo_metadata::MetadataCommand;
 
 use crate::cargo_workspace_errors::CargoWorkspaceError;
 
+/// A package that is a member of the workspace
+#[derive(Clone, PartialEq, Debug)]
+pub struct WorkspacePackage
+{
+    pub name: String,
+    pub directory: Utf8PathBuf
+}
+
 pub fn projects(workspace: &Utf8Path) -> Result<Vec<Utf8PathBuf>, CargoWorkspaceError>
+{
+    let projects = packages(workspace)?.into_iter().map(|package| package.directory).collect();
+
+    Ok(projects)
+}
+
+pub fn packages(workspace: &Utf8Path) -> Result<Vec<WorkspacePackage>, CargoWorkspaceError>
 {
     let mut toml = workspace.to_path_buf();
 
@@ -34,13 +49,17 @@ pub fn projects(workspace: &Utf8Path) -> Result<Vec<Utf8PathBuf>, CargoWorkspace
 
     let metadata = MetadataCommand::new().manifest_path(toml).no_deps().exec()?;
 
-    let project_names = metadata
+    let packages = metadata
         .packages
         .iter()
         .filter(|package| metadata.workspace_members.contains(&package.id))
-        .map(|package| package.manifest_path.clone().to_path_buf())
-        .filter_map(|path| path.parent().map(|directory| directory.to_path_buf()))
+        .filter_map(|package| {
+            package.manifest_path.parent().map(|directory| WorkspacePackage {
+                name: package.name.to_string(),
+                directory: directory.to_path_buf()
+            })
+        })
         .collect();
 
-    Ok(project_names)
+    Ok(packages)
 }
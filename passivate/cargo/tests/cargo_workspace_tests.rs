@@ -28,6 +28,18 @@ pub fn query_projects_in_workspace()
     assert_that!(&projects, contains_in_order(vec![project_a_path, project_b_path]));
 }
 
+#[test]
+pub fn query_package_names_in_workspace()
+{
+    let workspace_path = get_default_workspace_path("simple_workspace");
+
+    let packages = cargo_workspace::packages(&workspace_path).unwrap();
+
+    let names: Vec<&str> = packages.iter().map(|package| package.name.as_str()).collect();
+
+    assert_that!(&names, contains_in_order(vec!["project_a", "project_b"]));
+}
+
 #[test]
 pub fn query_with_full_cargo_toml_path()
 {
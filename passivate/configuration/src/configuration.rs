@@ -1,13 +1,52 @@
+use std::time::Duration;
+
 use camino::Utf8PathBuf;
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 
+use crate::coverage_thresholds::CoverageThresholds;
+use crate::hyp_timeouts::HypTimeouts;
+use crate::ignored_hyps::IgnoredHyps;
+use crate::run_scope::RunScope;
+use crate::run_selection::RunSelection;
+
 #[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize, Parser)]
 pub struct PassivateConfiguration
 {
     pub passivate_directory: Option<Utf8PathBuf>,
     pub coverage_enabled: bool,
-    pub snapshot_directories: Vec<Utf8PathBuf>
+    pub snapshot_directories: Vec<Utf8PathBuf>,
+    #[arg(skip)]
+    #[serde(default)]
+    pub run_selection: RunSelection,
+    /// Run the hyps that failed last time before all other hyps
+    #[arg(skip)]
+    #[serde(default)]
+    pub failed_hyps_first: bool,
+    /// Hyps that take longer than this many milliseconds to run are flagged as slow
+    #[arg(skip)]
+    #[serde(default)]
+    pub slow_hyp_threshold_ms: Option<u64>,
+    /// How many times a failing hyp is retried, none leaves it to the nextest profile
+    #[arg(skip)]
+    #[serde(default)]
+    pub retries: Option<u32>,
+    #[arg(skip)]
+    #[serde(default)]
+    pub ignored_hyps: IgnoredHyps,
+    #[arg(skip)]
+    #[serde(default)]
+    pub timeouts: HypTimeouts,
+    /// The profile of the project's nextest configuration hyps run with, none uses the default profile
+    #[arg(skip)]
+    #[serde(default)]
+    pub nextest_profile: Option<String>,
+    #[arg(skip)]
+    #[serde(default)]
+    pub run_scope: RunScope,
+    #[arg(skip)]
+    #[serde(default)]
+    pub coverage_thresholds: CoverageThresholds
 }
 
 impl PassivateConfiguration
@@ -16,6 +55,11 @@ impl PassivateConfiguration
     {
         self.snapshot_directories.push(snapshot_directory);
     }
+
+    pub fn slow_hyp_threshold(&self) -> Option<Duration>
+    {
+        self.slow_hyp_threshold_ms.map(Duration::from_millis)
+    }
 }
 
 // TODO: Generate this
@@ -25,7 +69,15 @@ pub enum ConfigurationChange
     PassivateDirectory(Option<Utf8PathBuf>),
     CoverageEnabled(bool),
     SnapshotDirectories(Vec<Utf8PathBuf>),
-    AddSnapshotDirectory(Utf8PathBuf)
+    AddSnapshotDirectory(Utf8PathBuf),
+    RunSelection(RunSelection),
+    FailedHypsFirst(bool),
+    SlowHypThresholdMs(Option<u64>),
+    Retries(Option<u32>),
+    IgnoredHyps(IgnoredHyps),
+    Timeouts(HypTimeouts),
+    NextestProfile(Option<String>),
+    RunScope(RunScope)
 }
 
 impl PassivateConfiguration
@@ -37,7 +89,18 @@ impl PassivateConfiguration
             ConfigurationChange::PassivateDirectory(passivate_directory) => self.passivate_directory = passivate_directory,
             ConfigurationChange::CoverageEnabled(coverage_enabled) => self.coverage_enabled = coverage_enabled,
             ConfigurationChange::SnapshotDirectories(snapshot_directories) => self.snapshot_directories = snapshot_directories,
-            ConfigurationChange::AddSnapshotDirectory(snapshot_directory) => self.add_snapshot_directory(snapshot_directory)
+            ConfigurationChange::AddSnapshotDirectory(snapshot_directory) => self.add_snapshot_directory(snapshot_directory),
+            ConfigurationChange::RunSelection(run_selection) => self.run_selection = run_selection,
+            ConfigurationChange::FailedHypsFirst(failed_hyps_first) => self.failed_hyps_first = failed_hyps_first,
+            ConfigurationChange::SlowHypThresholdMs(slow_hyp_threshold_ms) =>
+            {
+                self.slow_hyp_threshold_ms = slow_hyp_threshold_ms
+            }
+            ConfigurationChange::Retries(retries) => self.retries = retries,
+            ConfigurationChange::IgnoredHyps(ignored_hyps) => self.ignored_hyps = ignored_hyps,
+            ConfigurationChange::Timeouts(timeouts) => self.timeouts = timeouts,
+            ConfigurationChange::NextestProfile(nextest_profile) => self.nextest_profile = nextest_profile,
+            ConfigurationChange::RunScope(run_scope) => self.run_scope = run_scope
         }
     }
 }
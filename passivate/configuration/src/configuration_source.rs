@@ -91,6 +91,9 @@ where
 #[cfg(test)]
 mod tests
 {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
     use camino::Utf8PathBuf;
     use galvanic_assert::matchers::collection::contains_in_order;
     use galvanic_assert::matchers::eq;
@@ -99,6 +102,11 @@ mod tests
 
     use crate::configuration::PassivateConfiguration;
     use crate::configuration_source::{ConfigurationSource, FileConfigurationSource};
+    use crate::coverage_thresholds::CoverageThresholds;
+    use crate::hyp_timeouts::HypTimeouts;
+    use crate::ignored_hyps::IgnoredHyps;
+    use crate::run_scope::RunScope;
+    use crate::run_selection::RunSelection;
 
     #[test]
     pub fn load_configuration_from_toml_file()
@@ -120,4 +128,170 @@ mod tests
             })
         );
     }
+
+    #[test]
+    pub fn load_coverage_thresholds_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("coverage_thresholds_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        let expected = CoverageThresholds {
+            global: Some(80.0),
+            packages: BTreeMap::from([("sample_project".to_string(), 90.0)]),
+            files: BTreeMap::from([("src/**/*.rs".to_string(), 50.0)])
+        };
+
+        assert_eq!(expected, configuration.coverage_thresholds);
+    }
+
+    #[test]
+    pub fn coverage_thresholds_are_empty_when_not_configured()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("minimal_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert!(configuration.coverage_thresholds.is_empty());
+    }
+
+    #[test]
+    pub fn load_run_selection_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("run_selection_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(RunSelection::CoveredLines, configuration.run_selection);
+    }
+
+    #[test]
+    pub fn all_hyps_run_when_run_selection_is_not_configured()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("minimal_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(RunSelection::All, configuration.run_selection);
+    }
+
+    #[test]
+    pub fn load_slow_hyp_threshold_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("slow_hyp_threshold_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(Some(Duration::from_millis(500)), configuration.slow_hyp_threshold());
+    }
+
+    #[test]
+    pub fn no_hyp_is_slow_when_slow_hyp_threshold_is_not_configured()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("minimal_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(None, configuration.slow_hyp_threshold());
+    }
+
+    #[test]
+    pub fn load_retries_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("retries_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(Some(2), configuration.retries);
+    }
+
+    #[test]
+    pub fn load_ignored_hyps_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("ignored_hyps_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(IgnoredHyps::Include, configuration.ignored_hyps);
+    }
+
+    #[test]
+    pub fn ignored_hyps_are_skipped_when_not_configured()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("minimal_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(IgnoredHyps::Skip, configuration.ignored_hyps);
+    }
+
+    #[test]
+    pub fn load_timeouts_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("timeouts_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(
+            HypTimeouts {
+                period_ms: Some(2000),
+                terminate_after: Some(3)
+            },
+            configuration.timeouts
+        );
+        assert_eq!(Duration::from_secs(2), configuration.timeouts.period());
+    }
+
+    #[test]
+    pub fn load_nextest_profile_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("nextest_profile_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(Some(String::from("passivate")), configuration.nextest_profile);
+    }
+
+    #[test]
+    pub fn load_run_scope_from_toml_file()
+    {
+        let file_path = test_data_path()
+            .join("example_configurations")
+            .join("run_scope_configuration.toml");
+        let source = FileConfigurationSource::from(file_path);
+        let configuration: PassivateConfiguration = source.load().unwrap().unwrap();
+
+        assert_eq!(
+            RunScope {
+                packages: vec![String::from("sample_project")],
+                features: vec![String::from("subtract")],
+                release: true,
+                target: Some(String::from("x86_64-unknown-linux-gnu")),
+                ..Default::default()
+            },
+            configuration.run_scope
+        );
+    }
 }
@@ -0,0 +1,24 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum coverage percentages, a completed coverage run below any of them fails the session
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoverageThresholds
+{
+    /// Minimum coverage of the whole workspace
+    pub global: Option<f64>,
+    /// Minimum coverage per package, by package name
+    pub packages: BTreeMap<String, f64>,
+    /// Minimum coverage of every file matching a glob, relative to the workspace root
+    pub files: BTreeMap<String, f64>
+}
+
+impl CoverageThresholds
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.global.is_none() && self.packages.is_empty() && self.files.is_empty()
+    }
+}
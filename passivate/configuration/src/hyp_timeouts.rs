@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// When nextest considers a running hyp slow, and when it gives up on it
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HypTimeouts
+{
+    /// Milliseconds after which a running hyp is slow, and again each time that much longer passes
+    pub period_ms: Option<u64>,
+    /// How many periods a hyp runs for before it is terminated, none lets it run until it finishes
+    pub terminate_after: Option<u32>
+}
+
+impl HypTimeouts
+{
+    /// Nextest's own period, for when only termination is configured
+    const DEFAULT_PERIOD: Duration = Duration::from_secs(60);
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.period_ms.is_none() && self.terminate_after.is_none()
+    }
+
+    pub fn period(&self) -> Duration
+    {
+        self.period_ms.map_or(Self::DEFAULT_PERIOD, Duration::from_millis)
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether hyps marked `#[ignore]` run
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IgnoredHyps
+{
+    /// Ignored hyps are skipped
+    #[default]
+    Skip,
+    /// Only the ignored hyps run
+    Only,
+    /// Ignored hyps run along with every other hyp
+    Include
+}
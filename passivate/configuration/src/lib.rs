@@ -2,4 +2,9 @@ pub mod configuration;
 pub mod configuration_errors;
 pub mod configuration_manager;
 pub mod configuration_source;
+pub mod coverage_thresholds;
 pub mod default_paths;
+pub mod hyp_timeouts;
+pub mod ignored_hyps;
+pub mod run_scope;
+pub mod run_selection;
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Which packages, features and targets the hyps are built for
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunScope
+{
+    /// Packages to build the hyps of, none builds those cargo selects by default
+    pub packages: Vec<String>,
+    /// Packages of the workspace to leave out, which builds every other package of the workspace
+    pub exclude: Vec<String>,
+    pub features: Vec<String>,
+    pub all_features: bool,
+    pub no_default_features: bool,
+    /// Build every target rather than only those cargo tests by default
+    pub all_targets: bool,
+    pub release: bool,
+    /// The target triple to build for, none builds for the host
+    pub target: Option<String>
+}
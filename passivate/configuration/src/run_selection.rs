@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Which hyps run after source files change
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunSelection
+{
+    /// Every hyp in the workspace
+    #[default]
+    All,
+    /// Hyps of the packages owning the changed files, and of the packages depending on them
+    AffectedPackages,
+    /// Hyps whose last recorded coverage executed the changed lines, every hyp when that is unknown
+    CoveredLines
+}
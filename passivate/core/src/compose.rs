@@ -7,6 +7,10 @@ use passivate_configuration::configuration_errors::ConfigurationError;
 use passivate_configuration::configuration_manager::ConfigurationManager;
 use passivate_configuration::configuration_source::FileConfigurationSource;
 use passivate_configuration::default_paths::DefaultPaths;
+use passivate_coverage::compute_coverage::ComputeCoverage;
+use passivate_coverage::coverage_status::CoverageStatus;
+use passivate_coverage::grcov::{self, Grcov};
+use passivate_coverage::llvm_cov::LlvmCov;
 use passivate_log::log_message::LogMessage;
 use passivate_log::tx_log::TxLog;
 use passivate_model_bridge::hyp_run_request::HypRunRequest;
@@ -33,6 +37,7 @@ pub struct PassivateCore
     pub source_change_rx: crossbeam_channel::Receiver<SourceChangeEvent>,
     pub hyp_run_tx: tokio::sync::mpsc::Sender<HypRunRequest<RustBridge>>,
     pub session_event_rx: crossbeam_channel::Receiver<HypSessionEvent<RustBridge>>,
+    pub coverage_rx: crossbeam_channel::Receiver<CoverageStatus>,
     pub configuration: ConfigurationManager,
     pub log_rx: crossbeam_channel::Receiver<LogMessage>,
     change_events: NotifyChangeEvents,
@@ -58,6 +63,9 @@ pub fn compose(args: PassivateArgs, runtime: &Runtime) -> Result<PassivateCore,
     // Send status of hyp run to the session
     let (session_event_tx, session_event_rx) = crossbeam_channel::unbounded();
 
+    // Send status of coverage computation to the state
+    let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
     // Send requests to run hyps
     let (hyp_run_tx, hyp_run_rx) = tokio::sync::mpsc::channel(1);
 
@@ -79,12 +87,29 @@ pub fn compose(args: PassivateArgs, runtime: &Runtime) -> Result<PassivateCore,
 
     let hyp_runner = HypRunner;
 
+    // Fall back to reading llvm-cov output directly when grcov is not available
+    let compute_coverage: Box<dyn ComputeCoverage + Send + Sync> = if grcov::is_installed()
+    {
+        Box::new(Grcov)
+    }
+    else
+    {
+        Box::new(LlvmCov)
+    };
+
     let configuration_path = args
         .config_directory
         .unwrap_or(default_paths.root.join(".config"))
         .join("passivate.toml");
 
-    let hyp_run_task = hyp_run_handler::spawn_hyp_run_future(runtime, hyp_run_rx, session_event_tx, hyp_runner);
+    let hyp_run_task = hyp_run_handler::spawn_hyp_run_future(
+        runtime,
+        hyp_run_rx,
+        session_event_tx,
+        hyp_runner,
+        compute_coverage,
+        coverage_tx
+    );
 
     // Notify
     let change_events = NotifyChangeEvents::start_watching(default_paths.root.clone(), source_change_tx)?;
@@ -101,6 +126,7 @@ pub fn compose(args: PassivateArgs, runtime: &Runtime) -> Result<PassivateCore,
         source_change_rx,
         hyp_run_tx,
         session_event_rx,
+        coverage_rx,
         configuration,
         log_rx,
         change_events,
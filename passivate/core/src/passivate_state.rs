@@ -1,4 +1,7 @@
+use passivate_coverage::coverage_diff::CoverageDiff;
 use passivate_coverage::coverage_status::CoverageStatus;
+use passivate_coverage::grcov::covdir_json::CovdirJson;
+use passivate_delegation::tx_rx::Rx;
 use passivate_model_bridge::bridge::Bridge;
 
 use crate::passivate_state_change::PassivateStateChange;
@@ -6,7 +9,10 @@ use crate::passivate_state_change::PassivateStateChange;
 pub struct PassivateState<TBridge: Bridge>
 {
     pub selected_hyp: Option<TBridge::Id>,
-    pub coverage: CoverageStatus
+    pub coverage: CoverageStatus,
+    /// Change in coverage between the latest two completed coverage runs
+    pub coverage_diff: Option<CoverageDiff>,
+    latest_coverage: Option<Box<CovdirJson>>
 }
 
 impl<TBridge: Bridge> PassivateState<TBridge>
@@ -24,12 +30,34 @@ impl<TBridge: Bridge> PassivateState<TBridge>
         }
     }
 
+    pub fn update_coverage(&mut self, coverage_rx: &impl Rx<CoverageStatus>)
+    {
+        while let Ok(coverage) = coverage_rx.try_recv()
+        {
+            if let CoverageStatus::Done(current) = &coverage
+            {
+                if let Some(previous) = &self.latest_coverage
+                {
+                    self.coverage_diff = Some(CoverageDiff::between(previous, current));
+                }
+
+                self.latest_coverage = Some(current.clone());
+            }
+
+            self.coverage = coverage;
+        }
+    }
+
     fn process_change(&mut self, change: &PassivateStateChange<'_, TBridge>)
     {
         match change
         {
             PassivateStateChange::HypSelected(hyp) => self.selected_hyp = Some(hyp.id().clone()),
-            PassivateStateChange::HypDetailsChanged(_hyp) => todo!(),
+            // The selected hyp is tracked by id, so the session already has its latest details
+            PassivateStateChange::HypDetailsChanged(_hyp) =>
+            {}
+            PassivateStateChange::RunHyp(_hyp) =>
+            {}
             PassivateStateChange::ConfigurationChanged(_configuration_change) => todo!()
         }
     }
@@ -41,7 +69,9 @@ impl<TBridge: Bridge> Default for PassivateState<TBridge>
     {
         Self {
             selected_hyp: None,
-            coverage: CoverageStatus::Disabled
+            coverage: CoverageStatus::Disabled,
+            coverage_diff: None,
+            latest_coverage: None
         }
     }
 }
@@ -7,6 +7,8 @@ pub enum PassivateStateChange<'a, TBridge: Bridge>
 {
     HypSelected(&'a Hyp<TBridge>),
     HypDetailsChanged(&'a Hyp<TBridge>),
+    /// Run a single hyp on demand, even when it is ignored
+    RunHyp(&'a Hyp<TBridge>),
     ConfigurationChanged(ConfigurationChange)
 }
 
@@ -18,6 +20,7 @@ impl<'a, TBridge: Bridge> PassivateStateChange<'a, TBridge>
         {
             PassivateStateChange::HypSelected(_) => false,
             PassivateStateChange::HypDetailsChanged(_) => false,
+            PassivateStateChange::RunHyp(_) => false,
             PassivateStateChange::ConfigurationChanged(_) => true
         }
     }
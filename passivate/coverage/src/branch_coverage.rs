@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Coverage of a single branch, as reported by lcov 'BRDA' entries
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchCoverage
+{
+    pub line: u32,
+    pub block: u32,
+    pub branch: u32,
+    /// Number of times the branch was taken, 'None' when the enclosing block never executed
+    pub taken: Option<u64>
+}
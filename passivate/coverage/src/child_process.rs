@@ -0,0 +1,65 @@
+use std::process::{Child, ExitStatus};
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::coverage_errors::CoverageError;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Wait for a coverage tool to exit, killing it as soon as the cancellation is requested
+pub(crate) fn wait_or_kill(child: &mut Child, cancellation: &CancellationToken) -> Result<ExitStatus, CoverageError>
+{
+    loop
+    {
+        if cancellation.is_cancelled()
+        {
+            let _ = child.kill();
+            let _ = child.wait();
+
+            return Err(CoverageError::Cancelled);
+        }
+
+        if let Some(status) = child.try_wait().map_err(|e| CoverageError::FailedToGenerate(e.kind()))?
+        {
+            return Ok(status);
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::process::Command;
+
+    use tokio_util::sync::CancellationToken;
+
+    use crate::child_process::wait_or_kill;
+    use crate::coverage_errors::CoverageError;
+
+    #[test]
+    pub fn process_runs_to_completion_when_not_cancelled()
+    {
+        let mut child = Command::new("rustc").arg("--version").spawn().unwrap();
+
+        let status = wait_or_kill(&mut child, &CancellationToken::new()).unwrap();
+
+        assert!(status.success());
+    }
+
+    #[test]
+    pub fn process_is_killed_when_cancelled()
+    {
+        let mut child = Command::new("rustc").arg("--version").spawn().unwrap();
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+
+        let result = wait_or_kill(&mut child, &cancellation);
+
+        assert!(matches!(result, Err(CoverageError::Cancelled)));
+        assert!(child.try_wait().unwrap().is_some());
+    }
+}
@@ -1,18 +1,61 @@
+use tokio_util::sync::CancellationToken;
+
 use crate::coverage_errors::CoverageError;
+use crate::coverage_options::CoverageOptions;
 use crate::coverage_status::CoverageStatus;
+use crate::hyp_coverage::HypCoverage;
 
 #[mockall::automock]
 pub trait ComputeCoverage
 {
-    fn compute_coverage(&self) -> Result<CoverageStatus, CoverageError>;
-    fn clean_coverage_output(&self) -> Result<(), CoverageError>;
+    /// Coverage of the last run, killing the coverage tools and failing with 'CoverageError::Cancelled' when cancelled
+    fn compute_coverage(
+        &self,
+        options: &CoverageOptions,
+        cancellation: &CancellationToken
+    ) -> Result<CoverageStatus, CoverageError>;
+    /// Coverage of each hyp on its own, from the profiles recorded per hyp during the run
+    fn compute_hyp_coverage(
+        &self,
+        options: &CoverageOptions,
+        cancellation: &CancellationToken
+    ) -> Result<HypCoverage, CoverageError>;
+    fn clean_coverage_output(&self, options: &CoverageOptions) -> Result<(), CoverageError>;
+}
+
+impl<TComputeCoverage: ComputeCoverage + ?Sized> ComputeCoverage for Box<TComputeCoverage>
+{
+    fn compute_coverage(
+        &self,
+        options: &CoverageOptions,
+        cancellation: &CancellationToken
+    ) -> Result<CoverageStatus, CoverageError>
+    {
+        (**self).compute_coverage(options, cancellation)
+    }
+
+    fn compute_hyp_coverage(
+        &self,
+        options: &CoverageOptions,
+        cancellation: &CancellationToken
+    ) -> Result<HypCoverage, CoverageError>
+    {
+        (**self).compute_hyp_coverage(options, cancellation)
+    }
+
+    fn clean_coverage_output(&self, options: &CoverageOptions) -> Result<(), CoverageError>
+    {
+        (**self).clean_coverage_output(options)
+    }
 }
 
-pub fn stub() -> impl ComputeCoverage
+pub fn stub() -> MockComputeCoverage
 {
     let mut mock = MockComputeCoverage::new();
-    mock.expect_clean_coverage_output().returning(|| Ok(()));
-    mock.expect_compute_coverage().returning(|| Ok(CoverageStatus::Disabled));
+    mock.expect_clean_coverage_output().returning(|_| Ok(()));
+    mock.expect_compute_coverage().returning(|_, _| Ok(CoverageStatus::Disabled));
+    mock.expect_compute_hyp_coverage()
+        .returning(|_, _| Ok(HypCoverage::default()));
 
     mock
 }
@@ -0,0 +1,38 @@
+use mockall::mock;
+use passivate_delegation::tx_rx::Tx;
+
+use crate::coverage_status::CoverageStatus;
+
+/// Interface from a coverage implementation to communicate changes to the coverage state.
+pub trait SendCoverageBridge: Send + Sync + 'static
+{
+    fn send_coverage(&mut self, coverage: CoverageStatus);
+}
+
+impl<TTx> SendCoverageBridge for TTx
+where
+    TTx: Tx<CoverageStatus> + Send + Sync + 'static
+{
+    fn send_coverage(&mut self, coverage: CoverageStatus)
+    {
+        self.send(coverage);
+    }
+}
+
+mock! {
+    pub CoverageBridge { }
+
+    impl SendCoverageBridge for CoverageBridge
+    {
+        fn send_coverage(&mut self, coverage: CoverageStatus);
+    }
+}
+
+pub fn stub() -> MockCoverageBridge
+{
+    let mut mock = MockCoverageBridge::new();
+
+    mock.expect_send_coverage().return_const(());
+
+    mock
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::grcov::covdir_json::CovdirJson;
+use crate::line_coverage::LineCoverage;
+
+/// How the coverage of a file or directory changed since the previous run
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct CoverageDelta
+{
+    pub newly_covered: usize,
+    pub newly_uncovered: usize,
+    pub percent_delta: f64
+}
+
+impl CoverageDelta
+{
+    pub fn is_regression(&self) -> bool
+    {
+        self.newly_uncovered > 0 || self.percent_delta < 0.0
+    }
+
+    pub fn is_improvement(&self) -> bool
+    {
+        !self.is_regression() && (self.newly_covered > 0 || self.percent_delta > 0.0)
+    }
+}
+
+/// Coverage changes between two runs, for every file and directory of the latest run
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct CoverageDiff
+{
+    deltas: HashMap<Utf8PathBuf, CoverageDelta>
+}
+
+impl CoverageDiff
+{
+    pub fn between(previous: &CovdirJson, current: &CovdirJson) -> Self
+    {
+        let mut diff = Self::default();
+        diff.add(Some(previous), current, Utf8Path::new(""));
+
+        diff
+    }
+
+    /// Change of a file or directory by its path relative to the root, the root itself being the empty path
+    pub fn get(&self, path: &Utf8Path) -> Option<&CoverageDelta>
+    {
+        self.deltas.get(path)
+    }
+
+    fn add(&mut self, previous: Option<&CovdirJson>, current: &CovdirJson, path: &Utf8Path) -> CoverageDelta
+    {
+        let mut delta = CoverageDelta {
+            percent_delta: current.coverage_percent - previous.map_or(0.0, |previous| previous.coverage_percent),
+            ..Default::default()
+        };
+
+        match &current.children
+        {
+            Some(children) =>
+            {
+                for (name, child) in children
+                {
+                    let previous_child = previous.and_then(|previous| previous.children.as_ref()?.get(name));
+                    let child_delta = self.add(previous_child, child, &path.join(name));
+
+                    delta.newly_covered += child_delta.newly_covered;
+                    delta.newly_uncovered += child_delta.newly_uncovered;
+                }
+            }
+            None =>
+            {
+                let previous_lines: Vec<LineCoverage> = previous.map(|previous| previous.lines().collect()).unwrap_or_default();
+
+                for (index, line) in current.lines().enumerate()
+                {
+                    let was_covered = matches!(previous_lines.get(index), Some(LineCoverage::Covered(_)));
+                    let is_covered = matches!(line, LineCoverage::Covered(_));
+
+                    match (was_covered, is_covered)
+                    {
+                        (false, true) => delta.newly_covered += 1,
+                        (true, false) => delta.newly_uncovered += 1,
+                        _ =>
+                        {}
+                    };
+                }
+            }
+        };
+
+        self.deltas.insert(path.to_path_buf(), delta);
+
+        delta
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::{Utf8Path, Utf8PathBuf};
+
+    use crate::coverage_diff::CoverageDiff;
+    use crate::grcov::covdir_json::CovdirJson;
+    use crate::lcov::{self, LcovRecord};
+
+    #[test]
+    pub fn newly_covered_lines_are_counted()
+    {
+        let previous = coverage(&[("src/lib.rs", &[(1, 1), (2, 0), (3, 0)])]);
+        let current = coverage(&[("src/lib.rs", &[(1, 1), (2, 4), (3, 1)])]);
+
+        let diff = CoverageDiff::between(&previous, &current);
+        let delta = diff.get(Utf8Path::new("src/lib.rs")).unwrap();
+
+        assert_eq!(2, delta.newly_covered);
+        assert_eq!(0, delta.newly_uncovered);
+        assert!(delta.is_improvement());
+    }
+
+    #[test]
+    pub fn newly_uncovered_lines_are_a_regression()
+    {
+        let previous = coverage(&[("src/lib.rs", &[(1, 1), (2, 1)])]);
+        let current = coverage(&[("src/lib.rs", &[(1, 1), (2, 0)])]);
+
+        let diff = CoverageDiff::between(&previous, &current);
+        let delta = diff.get(Utf8Path::new("src/lib.rs")).unwrap();
+
+        assert_eq!(1, delta.newly_uncovered);
+        assert_eq!(-50.0, delta.percent_delta);
+        assert!(delta.is_regression());
+    }
+
+    #[test]
+    pub fn directories_sum_the_changes_of_their_children()
+    {
+        let previous = coverage(&[("src/a.rs", &[(1, 0)]), ("src/b.rs", &[(1, 1)])]);
+        let current = coverage(&[("src/a.rs", &[(1, 1)]), ("src/b.rs", &[(1, 0)])]);
+
+        let diff = CoverageDiff::between(&previous, &current);
+        let delta = diff.get(Utf8Path::new("src")).unwrap();
+
+        assert_eq!(1, delta.newly_covered);
+        assert_eq!(1, delta.newly_uncovered);
+        assert_eq!(0.0, delta.percent_delta);
+    }
+
+    #[test]
+    pub fn new_file_has_all_covered_lines_newly_covered()
+    {
+        let previous = coverage(&[("src/a.rs", &[(1, 1)])]);
+        let current = coverage(&[("src/a.rs", &[(1, 1)]), ("src/b.rs", &[(1, 1), (2, 1)])]);
+
+        let diff = CoverageDiff::between(&previous, &current);
+
+        assert_eq!(2, diff.get(Utf8Path::new("src/b.rs")).unwrap().newly_covered);
+        assert_eq!(2, diff.get(Utf8Path::new("")).unwrap().newly_covered);
+    }
+
+    #[test]
+    pub fn unchanged_coverage_is_neither_regression_nor_improvement()
+    {
+        let previous = coverage(&[("src/lib.rs", &[(1, 1), (2, 0)])]);
+
+        let diff = CoverageDiff::between(&previous, &previous);
+        let delta = diff.get(Utf8Path::new("src/lib.rs")).unwrap();
+
+        assert!(!delta.is_regression());
+        assert!(!delta.is_improvement());
+    }
+
+    fn coverage(files: &[(&str, &[(u32, u64)])]) -> CovdirJson
+    {
+        lcov::to_covdir(files.iter().map(|(source_file, lines)| {
+            LcovRecord {
+                source_file: Utf8PathBuf::from(*source_file),
+                lines: lines.iter().copied().collect(),
+                ..Default::default()
+            }
+        }))
+    }
+}
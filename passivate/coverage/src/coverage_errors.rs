@@ -19,12 +19,42 @@ pub enum CoverageError
     #[error("coverage did not run - no profraw files were present")]
     NoProfrawFiles(NoProfrawFilesError),
 
+    #[error("grcov failed: {0}")]
+    Grcov(String),
+
     #[error("failed to read covdir output")]
     CovdirRead(IoErrorKind),
 
     #[error("failed to parse covdir output")]
     CovdirParse(String),
 
+    #[error("llvm-tools are not installed - run 'rustup component add llvm-tools'")]
+    LlvmToolsNotInstalled(IoErrorKind),
+
+    #[error("llvm-cov failed: {0}")]
+    LlvmCov(String),
+
+    #[error("failed to parse lcov output: {0}")]
+    LcovParse(String),
+
+    #[error("failed to read which hyps produced which profiles")]
+    HypProfilesRead(IoErrorKind),
+
+    #[error("failed to parse which hyps produced which profiles: {0}")]
+    HypProfilesParse(String),
+
+    #[error("failed to read which test binaries were built")]
+    TestBinariesRead(IoErrorKind),
+
+    #[error("failed to parse which test binaries were built: {0}")]
+    TestBinariesParse(String),
+
+    #[error("invalid coverage threshold glob: {0}")]
+    ThresholdGlob(String),
+
+    #[error("coverage was cancelled")]
+    Cancelled,
+
     #[error("unexpected failure parsing workspace metadata")]
     Workspace(#[from] CargoWorkspaceError)
 }
@@ -0,0 +1,9 @@
+use camino::Utf8PathBuf;
+
+#[derive(bon::Builder, Clone, Debug)]
+pub struct CoverageOptions
+{
+    pub workspace_path: Utf8PathBuf,
+    pub output_path: Utf8PathBuf,
+    pub binary_path: Utf8PathBuf
+}
@@ -7,5 +7,6 @@ pub enum CoverageStatus
     Preparing,
     Running,
     Done(Box<CovdirJson>),
+    Cancelled,
     Error(String)
 }
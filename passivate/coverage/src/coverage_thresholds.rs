@@ -0,0 +1,245 @@
+use std::fmt::Display;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use globset::Glob;
+use passivate_cargo::cargo_workspace;
+use passivate_configuration::coverage_thresholds::CoverageThresholds;
+
+use crate::coverage_errors::CoverageError;
+use crate::grcov::covdir_json::CovdirJson;
+
+/// A part of the workspace whose coverage is below the minimum configured for it
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoverageBelowThreshold
+{
+    pub scope: ThresholdScope,
+    pub coverage_percent: f64,
+    pub minimum_percent: f64
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ThresholdScope
+{
+    Global,
+    Package(String),
+    File(Utf8PathBuf)
+}
+
+impl Display for CoverageBelowThreshold
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match &self.scope
+        {
+            ThresholdScope::Global => write!(f, "total coverage"),
+            ThresholdScope::Package(name) => write!(f, "coverage of package '{name}'"),
+            ThresholdScope::File(path) => write!(f, "coverage of '{path}'")
+        }?;
+
+        write!(
+            f,
+            " is {}%, below the minimum of {}%",
+            self.coverage_percent, self.minimum_percent
+        )
+    }
+}
+
+/// Find every configured threshold that the coverage does not meet
+pub fn check_thresholds(
+    coverage: &CovdirJson,
+    thresholds: &CoverageThresholds,
+    workspace_path: &Utf8Path
+) -> Result<Vec<CoverageBelowThreshold>, CoverageError>
+{
+    let mut below_threshold = Vec::new();
+
+    if let Some(minimum_percent) = thresholds.global
+    {
+        check(&mut below_threshold, ThresholdScope::Global, coverage, minimum_percent);
+    }
+
+    // Only query the workspace when needed, it requires running 'cargo metadata'
+    if !thresholds.packages.is_empty()
+    {
+        for package in cargo_workspace::packages(workspace_path)?
+        {
+            let Some(minimum_percent) = thresholds.packages.get(&package.name)
+            else
+            {
+                continue;
+            };
+
+            let package_coverage = package
+                .directory
+                .strip_prefix(workspace_path)
+                .ok()
+                .and_then(|directory| coverage.find(directory));
+
+            if let Some(package_coverage) = package_coverage
+            {
+                check(
+                    &mut below_threshold,
+                    ThresholdScope::Package(package.name),
+                    package_coverage,
+                    *minimum_percent
+                );
+            }
+        }
+    }
+
+    for (pattern, minimum_percent) in &thresholds.files
+    {
+        let matcher = Glob::new(pattern)
+            .map_err(|e| CoverageError::ThresholdGlob(e.to_string()))?
+            .compile_matcher();
+
+        for (path, file_coverage) in files(coverage, Utf8Path::new(""))
+        {
+            if matcher.is_match(&path)
+            {
+                check(
+                    &mut below_threshold,
+                    ThresholdScope::File(path),
+                    file_coverage,
+                    *minimum_percent
+                );
+            }
+        }
+    }
+
+    Ok(below_threshold)
+}
+
+fn check(below_threshold: &mut Vec<CoverageBelowThreshold>, scope: ThresholdScope, coverage: &CovdirJson, minimum_percent: f64)
+{
+    if coverage.coverage_percent < minimum_percent
+    {
+        below_threshold.push(CoverageBelowThreshold {
+            scope,
+            coverage_percent: coverage.coverage_percent,
+            minimum_percent
+        });
+    }
+}
+
+fn files<'a>(coverage: &'a CovdirJson, path: &Utf8Path) -> Vec<(Utf8PathBuf, &'a CovdirJson)>
+{
+    match &coverage.children
+    {
+        Some(children) =>
+        {
+            children
+                .iter()
+                .flat_map(|(name, child)| files(child, &path.join(name)))
+                .collect()
+        }
+        None => vec![(path.to_path_buf(), coverage)]
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::BTreeMap;
+
+    use camino::{Utf8Path, Utf8PathBuf};
+    use passivate_configuration::coverage_thresholds::CoverageThresholds;
+
+    use crate::coverage_errors::CoverageError;
+    use crate::coverage_thresholds::{CoverageBelowThreshold, ThresholdScope, check_thresholds};
+    use crate::grcov::covdir_json::CovdirJson;
+    use crate::lcov::{self, LcovRecord};
+
+    #[test]
+    pub fn total_coverage_below_global_minimum()
+    {
+        let thresholds = CoverageThresholds {
+            global: Some(80.0),
+            ..Default::default()
+        };
+
+        let below_threshold = check_thresholds(&example_coverage(), &thresholds, Utf8Path::new("")).unwrap();
+
+        let expected = vec![CoverageBelowThreshold {
+            scope: ThresholdScope::Global,
+            coverage_percent: 75.0,
+            minimum_percent: 80.0
+        }];
+
+        assert_eq!(expected, below_threshold);
+    }
+
+    #[test]
+    pub fn coverage_meeting_every_minimum_is_fine()
+    {
+        let thresholds = CoverageThresholds {
+            global: Some(75.0),
+            files: BTreeMap::from([("src/**".to_string(), 50.0)]),
+            ..Default::default()
+        };
+
+        let below_threshold = check_thresholds(&example_coverage(), &thresholds, Utf8Path::new("")).unwrap();
+
+        assert!(below_threshold.is_empty());
+    }
+
+    #[test]
+    pub fn only_files_matching_glob_are_checked()
+    {
+        let thresholds = CoverageThresholds {
+            files: BTreeMap::from([("src/nested/*.rs".to_string(), 100.0)]),
+            ..Default::default()
+        };
+
+        let below_threshold = check_thresholds(&example_coverage(), &thresholds, Utf8Path::new("")).unwrap();
+
+        let scopes: Vec<ThresholdScope> = below_threshold.into_iter().map(|below| below.scope).collect();
+
+        assert_eq!(vec![ThresholdScope::File(Utf8PathBuf::from("src/nested/util.rs"))], scopes);
+    }
+
+    #[test]
+    pub fn invalid_glob_is_error()
+    {
+        let thresholds = CoverageThresholds {
+            files: BTreeMap::from([("src/[".to_string(), 100.0)]),
+            ..Default::default()
+        };
+
+        let result = check_thresholds(&example_coverage(), &thresholds, Utf8Path::new(""));
+
+        assert!(matches!(result, Err(CoverageError::ThresholdGlob(_))));
+    }
+
+    #[test]
+    pub fn below_threshold_describes_what_and_by_how_much()
+    {
+        let below_threshold = CoverageBelowThreshold {
+            scope: ThresholdScope::Package("example".to_string()),
+            coverage_percent: 42.5,
+            minimum_percent: 80.0
+        };
+
+        assert_eq!(
+            "coverage of package 'example' is 42.5%, below the minimum of 80%",
+            below_threshold.to_string()
+        );
+    }
+
+    fn example_coverage() -> CovdirJson
+    {
+        lcov::to_covdir([
+            record("src/lib.rs", &[(1, 1), (2, 1)]),
+            record("src/nested/util.rs", &[(1, 1), (2, 0)])
+        ])
+    }
+
+    fn record(source_file: &str, lines: &[(u32, u64)]) -> LcovRecord
+    {
+        LcovRecord {
+            source_file: Utf8PathBuf::from(source_file),
+            lines: lines.iter().copied().collect(),
+            ..Default::default()
+        }
+    }
+}
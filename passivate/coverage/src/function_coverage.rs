@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Coverage of a single function, as reported by lcov 'FN' and 'FNDA' entries
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionCoverage
+{
+    pub name: String,
+    pub line: u32,
+    pub hits: u64
+}
@@ -1,27 +1,101 @@
+use camino::Utf8Path;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+use crate::branch_coverage::BranchCoverage;
+use crate::function_coverage::FunctionCoverage;
+use crate::line_coverage::LineCoverage;
+
 #[derive(Default, Clone, PartialEq, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CovdirJson
 {
     pub children: Option<IndexMap<String, CovdirJson>>,
+    /// Hit count per line for files, where -1 marks a line that is not executable
+    pub coverage: Option<Vec<i64>>,
     pub coverage_percent: f64,
     pub lines_covered: i64,
     pub lines_missed: i64,
     pub lines_total: i64,
-    pub name: String
+    pub name: String,
+    /// Branch data for files, only available when reading lcov
+    pub branches: Option<Vec<BranchCoverage>>,
+    /// Function data for files, only available when reading lcov
+    pub functions: Option<Vec<FunctionCoverage>>
+}
+
+impl CovdirJson
+{
+    /// Coverage of each line in a file, in line order (empty for directories)
+    pub fn lines(&self) -> impl Iterator<Item = LineCoverage>
+    {
+        self.coverage.iter().flatten().map(|hits| LineCoverage::from(*hits))
+    }
+
+    /// Find a descendant by its path relative to this node
+    pub fn find(&self, path: &Utf8Path) -> Option<&CovdirJson>
+    {
+        path.components()
+            .try_fold(self, |node, component| node.children.as_ref()?.get(component.as_str()))
+    }
 }
 
 #[cfg(test)]
 pub mod tests
 {
+    use camino::Utf8Path;
+
     use crate::grcov::parse_covdir;
+    use crate::line_coverage::LineCoverage;
 
     #[test]
     pub fn parse()
     {
-        let json = r#"
+        let result = parse_covdir(EXAMPLE_COVDIR).unwrap();
+
+        assert_eq!(100.0, result.coverage_percent);
+    }
+
+    #[test]
+    pub fn parse_keeps_hit_count_per_line()
+    {
+        let result = parse_covdir(EXAMPLE_COVDIR).unwrap();
+
+        let lib = result.find(Utf8Path::new("src/lib.rs")).unwrap();
+
+        let expected = vec![
+            LineCoverage::NotExecutable,
+            LineCoverage::Covered(4),
+            LineCoverage::Covered(4),
+            LineCoverage::Covered(4),
+            LineCoverage::NotExecutable,
+            LineCoverage::Covered(2),
+            LineCoverage::Covered(2),
+            LineCoverage::Covered(2)
+        ];
+
+        assert_eq!(expected, lib.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    pub fn directories_have_no_line_coverage()
+    {
+        let result = parse_covdir(EXAMPLE_COVDIR).unwrap();
+
+        let src = result.find(Utf8Path::new("src")).unwrap();
+
+        assert_eq!(0, src.lines().count());
+    }
+
+    #[test]
+    pub fn find_missing_file_is_none()
+    {
+        let result = parse_covdir(EXAMPLE_COVDIR).unwrap();
+
+        assert!(result.find(Utf8Path::new("src/missing.rs")).is_none());
+    }
+
+    const EXAMPLE_COVDIR: &str = r#"
         {
             "children":{
                 "src":{
@@ -104,9 +178,4 @@ pub mod tests
             "linesTotal":18,
             "name":""
         }"#;
-
-        let result = parse_covdir(json).unwrap();
-
-        assert_eq!(100.0, result.coverage_percent);
-    }
 }
\ No newline at end of file
@@ -1,36 +1,37 @@
 pub mod covdir_json;
 
 use std::fs;
-use std::io::Error as IoError;
-use std::process::Command;
-use std::time::Duration;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read};
+use std::process::{Command, Stdio};
+use std::thread;
 
 use camino::{Utf8Path, Utf8PathBuf};
 use passivate_cargo::cargo_workspace;
+use tokio_util::sync::CancellationToken;
 
+use crate::child_process;
 use crate::compute_coverage::ComputeCoverage;
 use crate::coverage_errors::{CoverageError, NoProfrawFilesError, NoProfrawFilesKind};
+use crate::coverage_options::CoverageOptions;
 use crate::coverage_status::CoverageStatus;
 use crate::grcov::covdir_json::CovdirJson;
+use crate::hyp_coverage::HypCoverage;
+use crate::hyp_profiles::HypProfiles;
+use crate::llvm_cov::LlvmCov;
 
-#[derive(bon::Builder)]
-pub struct Grcov
-{
-    workspace_path: Utf8PathBuf,
-    output_path: Utf8PathBuf,
-    binary_path: Utf8PathBuf
-}
+#[derive(Clone)]
+pub struct Grcov;
 
 impl ComputeCoverage for Grcov
 {
-    fn compute_coverage(&self) -> Result<CoverageStatus, CoverageError>
+    fn compute_coverage(&self, options: &CoverageOptions, cancellation: &CancellationToken) -> Result<CoverageStatus, CoverageError>
     {
-        match get_profraw_count(&self.output_path)
+        match get_profraw_count(&options.output_path)
         {
             Ok(0) =>
             {
                 let error = NoProfrawFilesError {
-                    expected_path: self.output_path.clone(),
+                    expected_path: options.output_path.clone(),
                     kind: NoProfrawFilesKind::NoProfrawFilesExist
                 };
 
@@ -39,7 +40,7 @@ impl ComputeCoverage for Grcov
             Err(io_error) =>
             {
                 let error = NoProfrawFilesError {
-                    expected_path: self.output_path.clone(),
+                    expected_path: options.output_path.clone(),
                     kind: NoProfrawFilesKind::Io(io_error.kind())
                 };
 
@@ -49,68 +50,98 @@ impl ComputeCoverage for Grcov
             {}
         };
 
-        let projects = cargo_workspace::projects(&self.workspace_path)?;
+        let projects = cargo_workspace::projects(&options.workspace_path)?;
 
         let mut command = Command::new("grcov");
 
         command
-            .current_dir(&self.workspace_path)
-            .arg(&self.output_path)
+            .current_dir(&options.workspace_path)
+            .arg(&options.output_path)
             .arg("-s")
             .arg(".")
             .arg("--binary-path")
-            .arg(&self.binary_path)
+            .arg(&options.binary_path)
             .arg("-t")
             .arg("covdir,lcov")
             .arg("--branch")
             .arg("--ignore-not-existing")
             .arg("-o")
-            .arg(&self.output_path);
+            .arg(&options.output_path);
 
         for project in projects
         {
-            let keep = project.strip_prefix(&self.workspace_path).unwrap().join("src").join("*");
+            let keep = project.strip_prefix(&options.workspace_path).unwrap().join("src").join("*");
             command.arg("--keep-only").arg(keep);
         }
 
-        let mut grcov = command.spawn().map_err(|e| CoverageError::GrcovNotInstalled(e.kind()))?;
+        let mut grcov = command
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| CoverageError::GrcovNotInstalled(e.kind()))?;
 
-        loop
-        {
-            std::thread::sleep(Duration::from_millis(100));
+        // Drain stderr while waiting, so grcov cannot block on a full pipe
+        let mut stderr = grcov.stderr.take().expect("stderr should be piped");
+        let stderr_reader = thread::spawn(move || {
+            let mut output = String::new();
+            let _ = stderr.read_to_string(&mut output);
+            output
+        });
 
-            // if cancellation.is_cancelled()
-            // {
-            //     let _ = grcov.kill();
-            //     break;
-            // }
+        let status = child_process::wait_or_kill(&mut grcov, cancellation)?;
+        let stderr = stderr_reader.join().unwrap_or_default();
 
-            let exited = grcov.try_wait().map_err(|e| CoverageError::FailedToGenerate(e.kind()))?;
+        // The reports of a failed run would be those of the previous one
+        if !status.success()
+        {
+            remove_report(&options.output_path, "lcov").map_err(|e| CoverageError::CleanIncomplete(e.kind()))?;
 
-            if exited.is_some()
-            {
-                break;
-            }
+            return Err(CoverageError::Grcov(stderr.trim().to_string()));
         }
 
-        let covdir_path = self.output_path.join("covdir");
+        let covdir_path = options.output_path.join("covdir");
         let json = fs::read_to_string(&covdir_path).map_err(|e| CoverageError::CovdirRead(e.kind()))?;
         let parsed: CovdirJson = parse_covdir(&json)?;
 
         Ok(CoverageStatus::Done(Box::new(parsed)))
     }
 
-    fn clean_coverage_output(&self) -> Result<(), CoverageError>
+    fn compute_hyp_coverage(&self, options: &CoverageOptions, cancellation: &CancellationToken) -> Result<HypCoverage, CoverageError>
     {
-        if let Ok(false) = fs::exists(&self.output_path)
+        // grcov merges every profile it is given, so each hyp is exported with the llvm tools grcov relies on itself
+        LlvmCov.compute_hyp_coverage(options, cancellation)
+    }
+
+    fn clean_coverage_output(&self, options: &CoverageOptions) -> Result<(), CoverageError>
+    {
+        if let Ok(false) = fs::exists(&options.output_path)
         {
             return Ok(());
         }
 
-        remove_profraw_files(&self.output_path).map_err(|e| CoverageError::CleanIncomplete(e.kind()))
+        remove_profraw_files(&options.output_path)
+            .and_then(|_| HypProfiles::remove(&options.output_path))
+            .and_then(|_| remove_report(&options.output_path, "covdir"))
+            .map_err(|e| CoverageError::CleanIncomplete(e.kind()))
     }
 }
 
+/// Remove a report grcov wrote, so a run that writes none cannot be mistaken for it
+///
+/// The lcov report is only removed once grcov failed, since tools watching it error while it is missing.
+fn remove_report(output_path: &Utf8Path, report: &str) -> Result<(), IoError>
+{
+    match fs::remove_file(output_path.join(report))
+    {
+        Err(error) if error.kind() != IoErrorKind::NotFound => Err(error),
+        _ => Ok(())
+    }
+}
+
+pub fn is_installed() -> bool
+{
+    Command::new("grcov").arg("--version").output().is_ok()
+}
+
 pub fn parse_covdir(json: &str) -> Result<CovdirJson, CoverageError>
 {
     serde_json::from_str(json).map_err(|e| CoverageError::CovdirParse(e.to_string()))
@@ -118,29 +149,36 @@ pub fn parse_covdir(json: &str) -> Result<CovdirJson, CoverageError>
 
 pub fn get_profraw_count(path: &Utf8Path) -> Result<i32, IoError>
 {
-    let mut count = 0;
+    Ok(profraw_files(path)?.len() as i32)
+}
+
+/// Profile files in the directory, including those in the profile directories of hyps below it
+pub(crate) fn profraw_files(directory: &Utf8Path) -> Result<Vec<Utf8PathBuf>, IoError>
+{
+    let mut files = Vec::new();
 
-    for profraw in fs::read_dir(path)?
+    for entry in directory.read_dir_utf8()?
     {
-        if let Some(extension) = profraw?.path().extension()
-            && extension == "profraw"
+        let path = entry?.into_path();
+
+        if path.is_dir()
+        {
+            files.extend(profraw_files(&path)?);
+        }
+        else if path.extension() == Some("profraw")
         {
-            count += 1;
+            files.push(path);
         }
     }
 
-    Ok(count)
+    Ok(files)
 }
 
-fn remove_profraw_files(directory: &Utf8Path) -> Result<(), IoError>
+pub(crate) fn remove_profraw_files(directory: &Utf8Path) -> Result<(), IoError>
 {
-    for profraw in fs::read_dir(directory)?.flatten()
+    for profraw in profraw_files(directory)?
     {
-        if let Some(extension) = profraw.path().extension()
-            && extension == "profraw"
-        {
-            fs::remove_file(profraw.path())?;
-        }
+        fs::remove_file(profraw)?;
     }
 
     Ok(())
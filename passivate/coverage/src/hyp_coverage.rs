@@ -0,0 +1,219 @@
+use std::collections::{HashMap, HashSet};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use passivate_hyp_names::hyp_id::HypId;
+
+use crate::grcov::covdir_json::CovdirJson;
+use crate::line_changes;
+use crate::line_coverage::LineCoverage;
+
+/// Coverage of each hyp on its own, to find out which hyps execute a file or line
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct HypCoverage
+{
+    hyps: HashMap<HypId, CovdirJson>,
+    sources: HashMap<Utf8PathBuf, String>,
+    /// Sources changed after they were covered, whose coverage no longer tells which hyps execute them
+    outdated: HashSet<Utf8PathBuf>
+}
+
+impl HypCoverage
+{
+    pub fn insert(&mut self, hyp_id: HypId, coverage: CovdirJson)
+    {
+        self.hyps.insert(hyp_id, coverage);
+    }
+
+    /// Keep a source file as it was when covered, to find out later which of its lines changed
+    pub fn record_source(&mut self, path: Utf8PathBuf, content: String)
+    {
+        self.sources.insert(path, content);
+    }
+
+    /// The file changed while the coverage was kept, so the coverage is older than the file
+    pub fn mark_outdated(&mut self, path: Utf8PathBuf)
+    {
+        self.outdated.insert(path);
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.hyps.is_empty()
+    }
+
+    /// What a single hyp covers
+    pub fn covered_by(&self, hyp_id: &HypId) -> Option<&CovdirJson>
+    {
+        self.hyps.get(hyp_id)
+    }
+
+    /// Hyps that execute any line of a file
+    pub fn hyps_covering_file<'a>(&'a self, path: &'a Utf8Path) -> impl Iterator<Item = &'a HypId>
+    {
+        self.hyps
+            .iter()
+            .filter(move |(_, coverage)| coverage.find(path).is_some_and(|file| file.lines_covered > 0))
+            .map(|(hyp_id, _)| hyp_id)
+    }
+
+    /// Hyps that execute a line of a file, where lines start at 1
+    pub fn hyps_covering_line<'a>(&'a self, path: &'a Utf8Path, line: usize) -> impl Iterator<Item = &'a HypId>
+    {
+        self.hyps
+            .iter()
+            .filter(move |(_, coverage)| {
+                let line_coverage = line.checked_sub(1).and_then(|index| coverage.find(path)?.lines().nth(index));
+
+                matches!(line_coverage, Some(LineCoverage::Covered(_)))
+            })
+            .map(|(hyp_id, _)| hyp_id)
+    }
+
+    /// Hyps that executed the lines changed since the file was covered, or none when the file was not covered or its
+    /// coverage is outdated
+    pub fn hyps_impacted_by(&self, path: &Utf8Path, content: &str) -> Option<HashSet<HypId>>
+    {
+        if self.outdated.contains(path)
+        {
+            return None;
+        }
+
+        let covered_content = self.sources.get(path)?;
+
+        let impacted = line_changes::changed_lines(covered_content, content)
+            .into_iter()
+            .flatten()
+            .flat_map(|line| self.hyps_covering_line(path, line).cloned())
+            .collect();
+
+        Some(impacted)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashSet;
+
+    use camino::{Utf8Path, Utf8PathBuf};
+    use passivate_hyp_names::hyp_id::HypId;
+
+    use crate::hyp_coverage::HypCoverage;
+    use crate::lcov::{self, LcovRecord};
+
+    const EXAMPLE_SOURCE: &str = r#"pub fn add(left: u64, right: u64) -> u64 {
+    left + right
+}
+
+pub fn multiply(left: u64, right: u64) -> u64 {
+    left * right
+}
+"#;
+
+    #[test]
+    pub fn hyps_covering_line_only_includes_hyps_that_executed_it()
+    {
+        let hyp_coverage = example_hyp_coverage();
+
+        let covering: Vec<&HypId> = hyp_coverage.hyps_covering_line(Utf8Path::new("src/lib.rs"), 2).collect();
+
+        assert_eq!(vec![&add_hyp()], covering);
+    }
+
+    #[test]
+    pub fn hyps_covering_file_includes_every_hyp_that_executed_part_of_it()
+    {
+        let hyp_coverage = example_hyp_coverage();
+
+        let mut covering: Vec<String> = hyp_coverage
+            .hyps_covering_file(Utf8Path::new("src/lib.rs"))
+            .map(|hyp_id| hyp_id.to_string())
+            .collect();
+
+        covering.sort();
+
+        assert_eq!(vec![add_hyp().to_string(), multiply_hyp().to_string()], covering);
+    }
+
+    #[test]
+    pub fn hyps_covering_unknown_file_is_empty()
+    {
+        let hyp_coverage = example_hyp_coverage();
+
+        assert_eq!(0, hyp_coverage.hyps_covering_file(Utf8Path::new("src/other.rs")).count());
+    }
+
+    #[test]
+    pub fn hyps_impacted_by_a_change_are_those_covering_the_changed_lines()
+    {
+        let mut hyp_coverage = example_hyp_coverage();
+        hyp_coverage.record_source(Utf8PathBuf::from("src/lib.rs"), EXAMPLE_SOURCE.to_string());
+
+        let changed = EXAMPLE_SOURCE.replace("left + right", "left - right");
+
+        let impacted = hyp_coverage.hyps_impacted_by(Utf8Path::new("src/lib.rs"), &changed);
+
+        assert_eq!(Some(HashSet::from([add_hyp()])), impacted);
+    }
+
+    #[test]
+    pub fn hyps_impacted_by_a_change_to_an_uncovered_file_are_unknown()
+    {
+        let hyp_coverage = example_hyp_coverage();
+
+        assert_eq!(
+            None,
+            hyp_coverage.hyps_impacted_by(Utf8Path::new("src/lib.rs"), EXAMPLE_SOURCE)
+        );
+    }
+
+    #[test]
+    pub fn hyps_impacted_by_a_change_to_an_outdated_file_are_unknown()
+    {
+        let mut hyp_coverage = example_hyp_coverage();
+        hyp_coverage.record_source(Utf8PathBuf::from("src/lib.rs"), EXAMPLE_SOURCE.to_string());
+        hyp_coverage.mark_outdated(Utf8PathBuf::from("src/lib.rs"));
+
+        let changed = EXAMPLE_SOURCE.replace("left + right", "left - right");
+
+        assert_eq!(None, hyp_coverage.hyps_impacted_by(Utf8Path::new("src/lib.rs"), &changed));
+    }
+
+    #[test]
+    pub fn covered_by_is_the_coverage_of_that_hyp()
+    {
+        let hyp_coverage = example_hyp_coverage();
+
+        let covered = hyp_coverage.covered_by(&multiply_hyp()).unwrap();
+
+        assert_eq!(1, covered.lines_covered);
+    }
+
+    fn example_hyp_coverage() -> HypCoverage
+    {
+        let mut hyp_coverage = HypCoverage::default();
+        hyp_coverage.insert(add_hyp(), lcov::to_covdir([lib_record(&[(2, 1), (6, 0)])]));
+        hyp_coverage.insert(multiply_hyp(), lcov::to_covdir([lib_record(&[(2, 0), (6, 1)])]));
+
+        hyp_coverage
+    }
+
+    fn lib_record(lines: &[(u32, u64)]) -> LcovRecord
+    {
+        LcovRecord {
+            source_file: Utf8PathBuf::from("src/lib.rs"),
+            lines: lines.iter().copied().collect(),
+            ..Default::default()
+        }
+    }
+
+    fn add_hyp() -> HypId
+    {
+        HypId::new("example", "example", "add_works")
+    }
+
+    fn multiply_hyp() -> HypId
+    {
+        HypId::new("example", "example", "multiply_works")
+    }
+}
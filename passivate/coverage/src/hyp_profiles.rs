@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use passivate_hyp_names::hyp_id::HypId;
+use serde::{Deserialize, Serialize};
+
+use crate::coverage_errors::CoverageError;
+use crate::grcov::profraw_files;
+
+/// The profile (profraw) files each hyp produced during a coverage run, relative to the coverage directory
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct HypProfiles
+{
+    profiles: HashMap<HypId, Vec<Utf8PathBuf>>
+}
+
+#[derive(Serialize, Deserialize)]
+struct HypProfilesJson
+{
+    hyp: Vec<String>,
+    profiles: Vec<Utf8PathBuf>
+}
+
+impl HypProfiles
+{
+    pub const FILE_NAME: &str = "hyp_profiles.json";
+
+    pub fn record(&mut self, hyp_id: HypId, profiles: impl IntoIterator<Item = Utf8PathBuf>)
+    {
+        self.profiles.entry(hyp_id).or_default().extend(profiles);
+    }
+
+    pub fn profiles(&self, hyp_id: &HypId) -> &[Utf8PathBuf]
+    {
+        self.profiles.get(hyp_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&HypId, &[Utf8PathBuf])>
+    {
+        self.profiles.iter().map(|(hyp_id, profiles)| (hyp_id, profiles.as_slice()))
+    }
+
+    pub fn save(&self, directory: &Utf8Path) -> Result<(), IoError>
+    {
+        fs::write(directory.join(Self::FILE_NAME), self.to_json())
+    }
+
+    pub fn load(directory: &Utf8Path) -> Result<Self, CoverageError>
+    {
+        let json = fs::read_to_string(directory.join(Self::FILE_NAME)).map_err(|e| CoverageError::HypProfilesRead(e.kind()))?;
+
+        Self::parse(&json)
+    }
+
+    pub fn remove(directory: &Utf8Path) -> Result<(), IoError>
+    {
+        match fs::remove_file(directory.join(Self::FILE_NAME))
+        {
+            Err(error) if error.kind() != IoErrorKind::NotFound => Err(error),
+            _ => Ok(())
+        }
+    }
+
+    pub fn parse(json: &str) -> Result<Self, CoverageError>
+    {
+        let entries: Vec<HypProfilesJson> =
+            serde_json::from_str(json).map_err(|e| CoverageError::HypProfilesParse(e.to_string()))?;
+
+        let mut hyp_profiles = Self::default();
+
+        for entry in entries
+        {
+            let [package_id, crate_id, name @ ..] = entry.hyp.as_slice()
+            else
+            {
+                return Err(CoverageError::HypProfilesParse(format!("invalid hyp id: {:?}", entry.hyp)));
+            };
+
+            hyp_profiles.record(HypId::new(package_id, crate_id, name.join("::")), entry.profiles);
+        }
+
+        Ok(hyp_profiles)
+    }
+
+    pub fn to_json(&self) -> String
+    {
+        let entries: Vec<HypProfilesJson> = self
+            .profiles
+            .iter()
+            .map(|(hyp_id, profiles)| {
+                HypProfilesJson {
+                    hyp: hyp_id.to_vec(),
+                    profiles: profiles.clone()
+                }
+            })
+            .collect();
+
+        serde_json::to_string(&entries).expect("hyp profiles should always serialize")
+    }
+}
+
+/// Attributes profile files to hyps, from the directory the test process of each hyp wrote its profiles to
+pub struct HypProfileRecorder
+{
+    directory: Utf8PathBuf,
+    profiles: HypProfiles
+}
+
+impl HypProfileRecorder
+{
+    /// Start recording a run, whose profiles replace those of earlier runs once it finishes
+    pub fn start(directory: &Utf8Path) -> Self
+    {
+        Self {
+            directory: directory.to_path_buf(),
+            profiles: HypProfiles::default()
+        }
+    }
+
+    /// Record the profiles in the hyp's profile directory, relative to the coverage directory
+    pub fn record(&mut self, hyp_id: HypId, profile_directory: &Utf8Path)
+    {
+        let profiles = profraw_files(&self.directory.join(profile_directory)).unwrap_or_default();
+
+        self.profiles.record(
+            hyp_id,
+            profiles
+                .into_iter()
+                .filter_map(|profile| profile.file_name().map(|file_name| profile_directory.join(file_name)))
+        );
+    }
+
+    pub fn finish(self) -> Result<HypProfiles, IoError>
+    {
+        self.profiles.save(&self.directory)?;
+
+        Ok(self.profiles)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::Utf8PathBuf;
+    use passivate_hyp_names::hyp_id::HypId;
+
+    use crate::coverage_errors::CoverageError;
+    use crate::hyp_profiles::HypProfiles;
+
+    #[test]
+    pub fn parse_what_was_serialized()
+    {
+        let mut hyp_profiles = HypProfiles::default();
+        hyp_profiles.record(example_hyp(), vec![Utf8PathBuf::from("coverage-1-2.profraw")]);
+
+        let parsed = HypProfiles::parse(&hyp_profiles.to_json()).unwrap();
+
+        assert_eq!(hyp_profiles, parsed);
+    }
+
+    #[test]
+    pub fn unrecorded_hyp_has_no_profiles()
+    {
+        let hyp_profiles = HypProfiles::default();
+
+        assert!(hyp_profiles.profiles(&example_hyp()).is_empty());
+    }
+
+    #[test]
+    pub fn recording_a_hyp_again_adds_profiles()
+    {
+        let mut hyp_profiles = HypProfiles::default();
+        hyp_profiles.record(example_hyp(), vec![Utf8PathBuf::from("a.profraw")]);
+        hyp_profiles.record(example_hyp(), vec![Utf8PathBuf::from("b.profraw")]);
+
+        let expected = vec![Utf8PathBuf::from("a.profraw"), Utf8PathBuf::from("b.profraw")];
+
+        assert_eq!(expected, hyp_profiles.profiles(&example_hyp()));
+    }
+
+    #[test]
+    pub fn parse_incomplete_hyp_id_is_error()
+    {
+        let result = HypProfiles::parse(r#"[{ "hyp": ["package"], "profiles": [] }]"#);
+
+        assert!(matches!(result, Err(CoverageError::HypProfilesParse(_))));
+    }
+
+    fn example_hyp() -> HypId
+    {
+        HypId::new("example_package", "example_crate", "tests::example_hyp")
+    }
+}
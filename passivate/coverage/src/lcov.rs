@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use camino::Utf8PathBuf;
+use indexmap::IndexMap;
+
+use crate::branch_coverage::BranchCoverage;
+use crate::coverage_errors::CoverageError;
+use crate::function_coverage::FunctionCoverage;
+use crate::grcov::covdir_json::CovdirJson;
+
+/// Coverage of a single source file, one 'SF' to 'end_of_record' section of an lcov file
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LcovRecord
+{
+    pub source_file: Utf8PathBuf,
+    pub lines: BTreeMap<u32, u64>,
+    pub functions: Vec<FunctionCoverage>,
+    pub branches: Vec<BranchCoverage>
+}
+
+pub fn parse_lcov(lcov: &str) -> Result<Vec<LcovRecord>, CoverageError>
+{
+    let mut records = Vec::new();
+    let mut current: Option<LcovRecord> = None;
+
+    for (index, line) in lcov.lines().enumerate()
+    {
+        let line = line.trim();
+
+        if line.is_empty()
+        {
+            continue;
+        }
+
+        let parse_error = || CoverageError::LcovParse(format!("line {}: '{line}'", index + 1));
+
+        if line == "end_of_record"
+        {
+            records.push(current.take().ok_or_else(parse_error)?);
+            continue;
+        }
+
+        let (tag, value) = line.split_once(':').ok_or_else(parse_error)?;
+
+        if tag == "SF"
+        {
+            current = Some(LcovRecord {
+                source_file: Utf8PathBuf::from(value),
+                ..Default::default()
+            });
+
+            continue;
+        }
+
+        let record = match current.as_mut()
+        {
+            Some(record) => record,
+            // Entries outside of a record, such as 'TN', carry nothing we need
+            None => continue
+        };
+
+        let mut fields = value.split(',');
+
+        match tag
+        {
+            "DA" =>
+            {
+                let line_number = field(fields.next()).ok_or_else(parse_error)?;
+                let hits: u64 = field(fields.next()).ok_or_else(parse_error)?;
+
+                *record.lines.entry(line_number).or_default() += hits;
+            }
+            "FN" =>
+            {
+                let (line_number, name) = value.split_once(',').ok_or_else(parse_error)?;
+
+                record.functions.push(FunctionCoverage {
+                    name: name.to_string(),
+                    line: field(Some(line_number)).ok_or_else(parse_error)?,
+                    hits: 0
+                });
+            }
+            "FNDA" =>
+            {
+                let (hits, name) = value.split_once(',').ok_or_else(parse_error)?;
+                let hits: u64 = field(Some(hits)).ok_or_else(parse_error)?;
+
+                if let Some(function) = record.functions.iter_mut().find(|function| function.name == name)
+                {
+                    function.hits += hits;
+                }
+            }
+            "BRDA" =>
+            {
+                let line_number = field(fields.next()).ok_or_else(parse_error)?;
+                let block = field(fields.next()).ok_or_else(parse_error)?;
+                let branch = field(fields.next()).ok_or_else(parse_error)?;
+
+                let taken = match fields.next().ok_or_else(parse_error)?
+                {
+                    "-" => None,
+                    taken => Some(field(Some(taken)).ok_or_else(parse_error)?)
+                };
+
+                record.branches.push(BranchCoverage {
+                    line: line_number,
+                    block,
+                    branch,
+                    taken
+                });
+            }
+            // Summary entries (LF, LH, FNF, FNH, BRF, BRH) are recomputed from the detailed entries
+            _ =>
+            {}
+        };
+    }
+
+    Ok(records)
+}
+
+/// Arrange lcov records into a covdir tree, with a directory node for every path component
+pub fn to_covdir(records: impl IntoIterator<Item = LcovRecord>) -> CovdirJson
+{
+    let mut records: Vec<LcovRecord> = records.into_iter().collect();
+    records.sort_by(|a, b| a.source_file.cmp(&b.source_file));
+
+    let mut root = directory_covdir("");
+
+    for record in records
+    {
+        let components: Vec<String> = record
+            .source_file
+            .components()
+            .map(|component| component.as_str().to_string())
+            .collect();
+
+        let Some((file_name, directories)) = components.split_last()
+        else
+        {
+            continue;
+        };
+
+        let mut node = &mut root;
+
+        for directory in directories
+        {
+            node = node
+                .children
+                .get_or_insert_default()
+                .entry(directory.clone())
+                .or_insert_with(|| directory_covdir(directory));
+        }
+
+        node.children
+            .get_or_insert_default()
+            .insert(file_name.clone(), file_covdir(file_name, record));
+    }
+
+    sum_directory_totals(&mut root);
+
+    root
+}
+
+fn field<T: FromStr>(value: Option<&str>) -> Option<T>
+{
+    value?.trim().parse().ok()
+}
+
+fn directory_covdir(name: &str) -> CovdirJson
+{
+    CovdirJson {
+        children: Some(IndexMap::new()),
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+fn file_covdir(name: &str, record: LcovRecord) -> CovdirJson
+{
+    let line_count = record.lines.keys().max().copied().unwrap_or(0) as usize;
+    let mut coverage = vec![-1; line_count];
+
+    for (line_number, hits) in &record.lines
+    {
+        // lcov line numbers start at 1
+        if let Some(line) = (*line_number as usize).checked_sub(1)
+        {
+            coverage[line] = *hits as i64;
+        }
+    }
+
+    let lines_total = record.lines.len() as i64;
+    let lines_covered = record.lines.values().filter(|hits| **hits > 0).count() as i64;
+
+    CovdirJson {
+        children: None,
+        coverage: Some(coverage),
+        coverage_percent: percent(lines_covered, lines_total),
+        lines_covered,
+        lines_missed: lines_total - lines_covered,
+        lines_total,
+        name: name.to_string(),
+        branches: Some(record.branches),
+        functions: Some(record.functions)
+    }
+}
+
+fn sum_directory_totals(node: &mut CovdirJson)
+{
+    let Some(children) = &mut node.children
+    else
+    {
+        return;
+    };
+
+    for child in children.values_mut()
+    {
+        sum_directory_totals(child);
+    }
+
+    node.lines_covered = children.values().map(|child| child.lines_covered).sum();
+    node.lines_missed = children.values().map(|child| child.lines_missed).sum();
+    node.lines_total = children.values().map(|child| child.lines_total).sum();
+    node.coverage_percent = percent(node.lines_covered, node.lines_total);
+}
+
+fn percent(covered: i64, total: i64) -> f64
+{
+    if total == 0
+    {
+        return 0.0;
+    }
+
+    // Rounded to two decimals, like grcov
+    (covered as f64 / total as f64 * 10000.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::Utf8Path;
+
+    use crate::branch_coverage::BranchCoverage;
+    use crate::coverage_errors::CoverageError;
+    use crate::function_coverage::FunctionCoverage;
+    use crate::lcov::{parse_lcov, to_covdir};
+    use crate::line_coverage::LineCoverage;
+
+    #[test]
+    pub fn parse_one_record_per_source_file()
+    {
+        let records = parse_lcov(EXAMPLE_LCOV).unwrap();
+
+        let source_files: Vec<&str> = records.iter().map(|record| record.source_file.as_str()).collect();
+
+        assert_eq!(vec!["src/lib.rs", "src/nested/util.rs"], source_files);
+    }
+
+    #[test]
+    pub fn parse_function_hits()
+    {
+        let records = parse_lcov(EXAMPLE_LCOV).unwrap();
+
+        let expected = vec![
+            FunctionCoverage {
+                name: "add".to_string(),
+                line: 2,
+                hits: 3
+            },
+            FunctionCoverage {
+                name: "multiply".to_string(),
+                line: 6,
+                hits: 0
+            },
+        ];
+
+        assert_eq!(expected, records[0].functions);
+    }
+
+    #[test]
+    pub fn parse_branches_including_never_executed()
+    {
+        let records = parse_lcov(EXAMPLE_LCOV).unwrap();
+
+        let expected = vec![
+            BranchCoverage {
+                line: 3,
+                block: 0,
+                branch: 0,
+                taken: Some(2)
+            },
+            BranchCoverage {
+                line: 3,
+                block: 0,
+                branch: 1,
+                taken: Some(1)
+            },
+            BranchCoverage {
+                line: 7,
+                block: 0,
+                branch: 0,
+                taken: None
+            },
+        ];
+
+        assert_eq!(expected, records[0].branches);
+    }
+
+    #[test]
+    pub fn parse_entry_outside_of_record_is_error()
+    {
+        let result = parse_lcov("SF:src/lib.rs\nend_of_record\nend_of_record");
+
+        assert!(matches!(result, Err(CoverageError::LcovParse(_))));
+    }
+
+    #[test]
+    pub fn parse_malformed_line_data_is_error()
+    {
+        let result = parse_lcov("SF:src/lib.rs\nDA:one,1\nend_of_record");
+
+        assert!(matches!(result, Err(CoverageError::LcovParse(_))));
+    }
+
+    #[test]
+    pub fn covdir_has_hit_count_per_line()
+    {
+        let covdir = to_covdir(parse_lcov(EXAMPLE_LCOV).unwrap());
+
+        let lib = covdir.find(Utf8Path::new("src/lib.rs")).unwrap();
+
+        let expected = vec![
+            LineCoverage::NotExecutable,
+            LineCoverage::Covered(3),
+            LineCoverage::Covered(3),
+            LineCoverage::NotExecutable,
+            LineCoverage::NotExecutable,
+            LineCoverage::Missed,
+            LineCoverage::Missed,
+        ];
+
+        assert_eq!(expected, lib.lines().collect::<Vec<_>>());
+        assert_eq!(2, lib.lines_covered);
+        assert_eq!(2, lib.lines_missed);
+        assert_eq!(50.0, lib.coverage_percent);
+    }
+
+    #[test]
+    pub fn covdir_directories_sum_their_children()
+    {
+        let covdir = to_covdir(parse_lcov(EXAMPLE_LCOV).unwrap());
+
+        let src = covdir.find(Utf8Path::new("src")).unwrap();
+
+        assert_eq!(5, src.lines_total);
+        assert_eq!(3, src.lines_covered);
+        assert_eq!(60.0, src.coverage_percent);
+        assert_eq!(60.0, covdir.coverage_percent);
+    }
+
+    #[test]
+    pub fn covdir_keeps_nested_directories()
+    {
+        let covdir = to_covdir(parse_lcov(EXAMPLE_LCOV).unwrap());
+
+        let util = covdir.find(Utf8Path::new("src/nested/util.rs")).unwrap();
+
+        assert_eq!("util.rs", util.name);
+        assert_eq!(100.0, util.coverage_percent);
+    }
+
+    const EXAMPLE_LCOV: &str = r#"
+TN:
+SF:src/lib.rs
+FN:2,add
+FN:6,multiply
+FNDA:3,add
+FNDA:0,multiply
+FNF:2
+FNH:1
+BRDA:3,0,0,2
+BRDA:3,0,1,1
+BRDA:7,0,0,-
+BRF:3
+BRH:2
+DA:2,3
+DA:3,3
+DA:6,0
+DA:7,0
+LF:4
+LH:2
+end_of_record
+TN:
+SF:src/nested/util.rs
+FN:1,repeat
+FNDA:1,repeat
+DA:1,1
+LF:1
+LH:1
+end_of_record
+"#;
+}
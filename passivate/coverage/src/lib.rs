@@ -1,4 +1,18 @@
+pub mod branch_coverage;
+mod child_process;
 pub mod compute_coverage;
+pub mod coverage_bridge;
+pub mod coverage_diff;
 pub mod coverage_errors;
+pub mod coverage_options;
 pub mod coverage_status;
+pub mod coverage_thresholds;
+pub mod function_coverage;
 pub mod grcov;
+pub mod hyp_coverage;
+pub mod hyp_profiles;
+pub mod lcov;
+pub mod line_changes;
+pub mod line_coverage;
+pub mod llvm_cov;
+pub mod test_binaries;
@@ -0,0 +1,86 @@
+use std::ops::RangeInclusive;
+
+/// Lines of the previous content that changed to become the current content, where lines start at 1
+///
+/// Everything from the first to the last differing line counts as changed, and content inserted between two
+/// lines changes both of them. Returns none when the content is the same.
+pub fn changed_lines(previous: &str, current: &str) -> Option<RangeInclusive<usize>>
+{
+    let previous: Vec<&str> = previous.lines().collect();
+    let current: Vec<&str> = current.lines().collect();
+
+    let common_prefix = previous
+        .iter()
+        .zip(&current)
+        .take_while(|(previous, current)| previous == current)
+        .count();
+
+    let common_suffix = previous[common_prefix ..]
+        .iter()
+        .rev()
+        .zip(current[common_prefix ..].iter().rev())
+        .take_while(|(previous, current)| previous == current)
+        .count();
+
+    let removed = previous.len() - common_prefix - common_suffix;
+    let added = current.len() - common_prefix - common_suffix;
+
+    match (removed, added)
+    {
+        (0, 0) => None,
+        (0, _) =>
+        {
+            let first = common_prefix.max(1);
+            let last = (common_prefix + 1).min(previous.len()).max(first);
+
+            Some(first ..= last)
+        }
+        (removed, _) => Some(common_prefix + 1 ..= common_prefix + removed)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::line_changes::changed_lines;
+
+    const PREVIOUS: &str = "fn add(left: u64, right: u64) -> u64\n{\n    left + right\n}\n";
+
+    #[test]
+    pub fn same_content_has_no_changed_lines()
+    {
+        assert_eq!(None, changed_lines(PREVIOUS, PREVIOUS));
+    }
+
+    #[test]
+    pub fn modified_line_is_changed()
+    {
+        let current = PREVIOUS.replace("left + right", "left - right");
+
+        assert_eq!(Some(3 ..= 3), changed_lines(PREVIOUS, &current));
+    }
+
+    #[test]
+    pub fn removed_lines_are_changed()
+    {
+        let current = "fn add(left: u64, right: u64) -> u64\n}\n";
+
+        assert_eq!(Some(2 ..= 3), changed_lines(PREVIOUS, current));
+    }
+
+    #[test]
+    pub fn lines_around_inserted_content_are_changed()
+    {
+        let current = PREVIOUS.replace("{\n", "{\n    println!(\"adding\");\n");
+
+        assert_eq!(Some(2 ..= 3), changed_lines(PREVIOUS, &current));
+    }
+
+    #[test]
+    pub fn content_appended_at_the_end_changes_the_last_line()
+    {
+        let current = format!("{PREVIOUS}\nfn subtract() {{}}\n");
+
+        assert_eq!(Some(4 ..= 4), changed_lines(PREVIOUS, &current));
+    }
+}
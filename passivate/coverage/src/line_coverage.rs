@@ -0,0 +1,21 @@
+/// Coverage of a single source line, as reported per line by grcov
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineCoverage
+{
+    NotExecutable,
+    Missed,
+    Covered(u64)
+}
+
+impl From<i64> for LineCoverage
+{
+    fn from(hits: i64) -> Self
+    {
+        match hits
+        {
+            hits if hits < 0 => LineCoverage::NotExecutable,
+            0 => LineCoverage::Missed,
+            hits => LineCoverage::Covered(hits as u64)
+        }
+    }
+}
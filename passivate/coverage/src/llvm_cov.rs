@@ -0,0 +1,304 @@
+use std::collections::BTreeSet;
+use std::env::consts::EXE_SUFFIX;
+use std::fs::{self, File};
+use std::io::{ErrorKind as IoErrorKind, Read};
+use std::num::NonZeroUsize;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use passivate_cargo::cargo_workspace;
+use passivate_hyp_names::hyp_id::HypId;
+use tokio_util::sync::CancellationToken;
+
+use crate::child_process;
+use crate::compute_coverage::ComputeCoverage;
+use crate::coverage_errors::{CoverageError, NoProfrawFilesError, NoProfrawFilesKind};
+use crate::coverage_options::CoverageOptions;
+use crate::coverage_status::CoverageStatus;
+use crate::grcov::{profraw_files, remove_profraw_files};
+use crate::hyp_coverage::HypCoverage;
+use crate::hyp_profiles::HypProfiles;
+use crate::lcov::{self, LcovRecord};
+use crate::test_binaries::TestBinaries;
+
+/// Computes coverage with the llvm tools shipped by rustup, without needing grcov
+#[derive(Clone)]
+pub struct LlvmCov;
+
+impl ComputeCoverage for LlvmCov
+{
+    fn compute_coverage(
+        &self,
+        options: &CoverageOptions,
+        cancellation: &CancellationToken
+    ) -> Result<CoverageStatus, CoverageError>
+    {
+        let profraw_files =
+            profraw_files(&options.output_path).map_err(|e| no_profraw_files(options, NoProfrawFilesKind::Io(e.kind())))?;
+
+        if profraw_files.is_empty()
+        {
+            return Err(no_profraw_files(options, NoProfrawFilesKind::NoProfrawFilesExist));
+        }
+
+        let lcov = export_lcov(
+            options,
+            &profraw_files,
+            &options.output_path.join("coverage.profdata"),
+            &options.output_path.join("lcov"),
+            cancellation
+        )?;
+
+        let projects = cargo_workspace::projects(&options.workspace_path)?;
+        let records = workspace_records(lcov::parse_lcov(&lcov)?, &options.workspace_path, &projects);
+
+        Ok(CoverageStatus::Done(Box::new(lcov::to_covdir(records))))
+    }
+
+    fn compute_hyp_coverage(
+        &self,
+        options: &CoverageOptions,
+        cancellation: &CancellationToken
+    ) -> Result<HypCoverage, CoverageError>
+    {
+        let hyp_profiles = HypProfiles::load(&options.output_path)?;
+        let projects = cargo_workspace::projects(&options.workspace_path)?;
+
+        let hyp_profiles: Vec<(&HypId, &[Utf8PathBuf])> =
+            hyp_profiles.iter().filter(|(_, profiles)| !profiles.is_empty()).collect();
+
+        // Every hyp is merged and exported on its own, so the hyps are divided over a worker per core
+        let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let chunk_size = hyp_profiles.len().div_ceil(workers).max(1);
+
+        let hyp_records = thread::scope(|scope| {
+            let workers: Vec<_> = hyp_profiles
+                .chunks(chunk_size)
+                .enumerate()
+                .map(|(worker, hyp_profiles)| {
+                    let projects = &projects;
+
+                    scope.spawn(move || {
+                        let profdata_path = options.output_path.join(format!("hyp-{worker}.profdata"));
+                        let lcov_path = options.output_path.join(format!("hyp-{worker}.lcov"));
+
+                        hyp_profiles
+                            .iter()
+                            .map(|(hyp_id, profiles)| {
+                                let profraw_files: Vec<Utf8PathBuf> =
+                                    profiles.iter().map(|profile| options.output_path.join(profile)).collect();
+
+                                let lcov = export_lcov(options, &profraw_files, &profdata_path, &lcov_path, cancellation)?;
+                                let records = workspace_records(lcov::parse_lcov(&lcov)?, &options.workspace_path, projects);
+
+                                Ok(((*hyp_id).clone(), records))
+                            })
+                            .collect::<Result<Vec<_>, CoverageError>>()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("hyp coverage worker should not panic"))
+                .collect::<Result<Vec<_>, CoverageError>>()
+        })?;
+
+        let mut hyp_coverage = HypCoverage::default();
+        let mut source_files = BTreeSet::new();
+
+        for (hyp_id, records) in hyp_records.into_iter().flatten()
+        {
+            source_files.extend(records.iter().map(|record| record.source_file.clone()));
+            hyp_coverage.insert(hyp_id, lcov::to_covdir(records));
+        }
+
+        for source_file in source_files
+        {
+            if let Ok(content) = fs::read_to_string(options.workspace_path.join(&source_file))
+            {
+                hyp_coverage.record_source(source_file, content);
+            }
+        }
+
+        Ok(hyp_coverage)
+    }
+
+    fn clean_coverage_output(&self, options: &CoverageOptions) -> Result<(), CoverageError>
+    {
+        if let Ok(false) = fs::exists(&options.output_path)
+        {
+            return Ok(());
+        }
+
+        remove_profraw_files(&options.output_path)
+            .and_then(|_| HypProfiles::remove(&options.output_path))
+            .and_then(|_| TestBinaries::remove(&options.output_path))
+            .map_err(|e| CoverageError::CleanIncomplete(e.kind()))
+    }
+}
+
+fn no_profraw_files(options: &CoverageOptions, kind: NoProfrawFilesKind) -> CoverageError
+{
+    CoverageError::NoProfrawFiles(NoProfrawFilesError {
+        expected_path: options.output_path.clone(),
+        kind
+    })
+}
+
+/// Merge profile files into a single profile and export the coverage it describes of the test binaries
+fn export_lcov(
+    options: &CoverageOptions,
+    profraw_files: &[Utf8PathBuf],
+    profdata_path: &Utf8Path,
+    lcov_path: &Utf8Path,
+    cancellation: &CancellationToken
+) -> Result<String, CoverageError>
+{
+    run_llvm_tool(
+        Command::new(llvm_tool("llvm-profdata"))
+            .arg("merge")
+            .arg("-sparse")
+            .args(profraw_files)
+            .arg("-o")
+            .arg(profdata_path),
+        cancellation
+    )?;
+
+    let test_binaries = TestBinaries::load(&options.output_path)?;
+
+    let Some((first_binary, other_binaries)) = test_binaries.binaries().split_first()
+    else
+    {
+        return Err(CoverageError::LlvmCov(String::from("no test binaries were built")));
+    };
+
+    let mut command = Command::new(llvm_tool("llvm-cov"));
+
+    command
+        .current_dir(&options.workspace_path)
+        .arg("export")
+        .arg("-format=lcov")
+        .arg(format!("-instr-profile={profdata_path}"))
+        .arg(first_binary);
+
+    for binary in other_binaries
+    {
+        command.arg("-object").arg(binary);
+    }
+
+    // The export can be large, so it goes to a file rather than a pipe that would fill up while polling for exit
+    let lcov_file = File::create(lcov_path).map_err(|e| CoverageError::FailedToGenerate(e.kind()))?;
+
+    run_llvm_tool(command.stdout(lcov_file), cancellation)?;
+
+    let lcov = fs::read(lcov_path).map_err(|e| CoverageError::FailedToGenerate(e.kind()))?;
+
+    Ok(String::from_utf8_lossy(&lcov).into_owned())
+}
+
+/// Find an llvm tool in the active toolchain, as installed by the 'llvm-tools' rustup component, falling back to the PATH
+fn llvm_tool(name: &str) -> Utf8PathBuf
+{
+    let file_name = format!("{name}{EXE_SUFFIX}");
+
+    Command::new("rustc")
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|sysroot| {
+            Utf8Path::new(sysroot.trim())
+                .join("lib")
+                .join("rustlib")
+                .read_dir_utf8()
+                .ok()?
+                .flatten()
+                .map(|target| target.path().join("bin").join(&file_name))
+                .find(|tool| tool.is_file())
+        })
+        .unwrap_or_else(|| Utf8PathBuf::from(file_name))
+}
+
+fn run_llvm_tool(command: &mut Command, cancellation: &CancellationToken) -> Result<(), CoverageError>
+{
+    let mut child = command.stderr(Stdio::piped()).spawn().map_err(|e| {
+        match e.kind()
+        {
+            IoErrorKind::NotFound => CoverageError::LlvmToolsNotInstalled(e.kind()),
+            kind => CoverageError::FailedToGenerate(kind)
+        }
+    })?;
+
+    // Drain stderr while waiting, so a chatty tool cannot block on a full pipe
+    let mut stderr = child.stderr.take().expect("stderr should be piped");
+    let stderr_reader = thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output);
+        output
+    });
+
+    let status = child_process::wait_or_kill(&mut child, cancellation)?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    if !status.success()
+    {
+        return Err(CoverageError::LlvmCov(stderr.trim().to_string()));
+    }
+
+    Ok(())
+}
+
+/// Keep only the sources of workspace projects, relative to the workspace, like grcov's '--keep-only'
+fn workspace_records(records: Vec<LcovRecord>, workspace_path: &Utf8Path, projects: &[Utf8PathBuf]) -> Vec<LcovRecord>
+{
+    records
+        .into_iter()
+        .filter_map(|mut record| {
+            let source_file = workspace_path.join(&record.source_file);
+
+            if !projects.iter().any(|project| source_file.starts_with(project.join("src")))
+            {
+                return None;
+            }
+
+            record.source_file = source_file.strip_prefix(workspace_path).ok()?.to_path_buf();
+
+            Some(record)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::{Utf8Path, Utf8PathBuf};
+
+    use crate::lcov::LcovRecord;
+    use crate::llvm_cov::workspace_records;
+
+    #[test]
+    pub fn only_workspace_project_sources_are_kept()
+    {
+        let records = vec![
+            record("/workspace/project/src/lib.rs"),
+            record("/workspace/project/tests/test.rs"),
+            record("/home/.cargo/registry/dependency/src/lib.rs"),
+        ];
+
+        let projects = vec![Utf8PathBuf::from("/workspace/project")];
+
+        let kept = workspace_records(records, Utf8Path::new("/workspace"), &projects);
+
+        assert_eq!(vec![record("project/src/lib.rs")], kept);
+    }
+
+    fn record(source_file: &str) -> LcovRecord
+    {
+        LcovRecord {
+            source_file: Utf8PathBuf::from(source_file),
+            ..Default::default()
+        }
+    }
+}
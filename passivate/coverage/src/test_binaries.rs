@@ -0,0 +1,93 @@
+use std::fs;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::coverage_errors::CoverageError;
+
+/// The test binaries cargo built for a coverage run, as reported in its JSON artifact messages
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TestBinaries
+{
+    binaries: Vec<Utf8PathBuf>
+}
+
+impl TestBinaries
+{
+    pub const FILE_NAME: &str = "test_binaries.json";
+
+    pub fn new(binaries: impl IntoIterator<Item = Utf8PathBuf>) -> Self
+    {
+        let mut binaries: Vec<Utf8PathBuf> = binaries.into_iter().collect();
+        binaries.sort();
+        binaries.dedup();
+
+        Self { binaries }
+    }
+
+    pub fn binaries(&self) -> &[Utf8PathBuf]
+    {
+        &self.binaries
+    }
+
+    pub fn save(&self, directory: &Utf8Path) -> Result<(), IoError>
+    {
+        fs::write(directory.join(Self::FILE_NAME), self.to_json())
+    }
+
+    pub fn load(directory: &Utf8Path) -> Result<Self, CoverageError>
+    {
+        let json =
+            fs::read_to_string(directory.join(Self::FILE_NAME)).map_err(|e| CoverageError::TestBinariesRead(e.kind()))?;
+
+        Self::parse(&json)
+    }
+
+    pub fn remove(directory: &Utf8Path) -> Result<(), IoError>
+    {
+        match fs::remove_file(directory.join(Self::FILE_NAME))
+        {
+            Err(error) if error.kind() != IoErrorKind::NotFound => Err(error),
+            _ => Ok(())
+        }
+    }
+
+    pub fn parse(json: &str) -> Result<Self, CoverageError>
+    {
+        serde_json::from_str(json).map_err(|e| CoverageError::TestBinariesParse(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> String
+    {
+        serde_json::to_string(self).expect("test binaries should always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::Utf8PathBuf;
+
+    use crate::test_binaries::TestBinaries;
+
+    #[test]
+    pub fn parse_what_was_serialized()
+    {
+        let test_binaries = TestBinaries::new(vec![Utf8PathBuf::from("target/debug/deps/add_tests-0123456789abcdef")]);
+
+        let parsed = TestBinaries::parse(&test_binaries.to_json()).unwrap();
+
+        assert_eq!(test_binaries, parsed);
+    }
+
+    #[test]
+    pub fn a_binary_built_for_several_targets_is_listed_once()
+    {
+        let binary = Utf8PathBuf::from("target/debug/deps/sample_project-0123456789abcdef");
+
+        let test_binaries = TestBinaries::new(vec![binary.clone(), binary.clone()]);
+
+        assert_eq!([binary], test_binaries.binaries());
+    }
+}
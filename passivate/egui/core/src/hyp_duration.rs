@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// A duration short enough to read at a glance, in milliseconds below a second and in seconds otherwise
+pub fn format_duration(duration: Duration) -> String
+{
+    if duration < Duration::from_secs(1)
+    {
+        format!("{} ms", duration.as_millis())
+    }
+    else
+    {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+pub fn is_slow(duration: Duration, slow_threshold: Option<Duration>) -> bool
+{
+    slow_threshold.is_some_and(|slow_threshold| duration > slow_threshold)
+}
@@ -1,3 +1,4 @@
+pub mod hyp_duration;
 pub mod log_entry;
 pub mod passivate_view_state;
 pub mod selected_hyp;
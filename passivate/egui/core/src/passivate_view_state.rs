@@ -78,7 +78,7 @@ impl<TBridge: Bridge> PassivateViewState<TBridge>
                     self.snapshot_handles = Self::check_for_snapshots(hyp, configuration, egui_context);
                 }
             }
-            PassivateStateChange::ConfigurationChanged(_) =>
+            PassivateStateChange::RunHyp(_) | PassivateStateChange::ConfigurationChanged(_) =>
             {}
         };
     }
@@ -1,6 +1,7 @@
 use camino::Utf8PathBuf;
 use egui::Ui;
 use passivate_configuration::configuration::{ConfigurationChange, PassivateConfiguration};
+use passivate_configuration::ignored_hyps::IgnoredHyps;
 
 #[derive(Default)]
 pub struct ConfigurationView
@@ -25,6 +26,20 @@ impl ConfigurationView
             actions.push(ConfigurationChange::CoverageEnabled(coverage_enabled));
         }
 
+        let mut ignored_hyps = configuration.ignored_hyps;
+
+        ui.horizontal(|ui| {
+            ui.label("Ignored Hyps");
+            ui.selectable_value(&mut ignored_hyps, IgnoredHyps::Skip, "Skip");
+            ui.selectable_value(&mut ignored_hyps, IgnoredHyps::Only, "Only");
+            ui.selectable_value(&mut ignored_hyps, IgnoredHyps::Include, "Include");
+        });
+
+        if ignored_hyps != configuration.ignored_hyps
+        {
+            actions.push(ConfigurationChange::IgnoredHyps(ignored_hyps));
+        }
+
         ui.label("Snapshot Directories");
 
         for snapshot_directory in &configuration.snapshot_directories
@@ -66,7 +81,8 @@ mod tests
         let configuration = PassivateConfiguration {
             passivate_directory: Some(Utf8PathBuf::from("some/alternative/.passivate")),
             coverage_enabled: true,
-            snapshot_directories: vec![Utf8PathBuf::from("tests/snapshots")]
+            snapshot_directories: vec![Utf8PathBuf::from("tests/snapshots")],
+            ..Default::default()
         };
 
         let ui = |ui: &mut egui::Ui| {
@@ -1,14 +1,32 @@
+pub mod source_view;
+
+use camino::{Utf8Path, Utf8PathBuf};
 use egui::collapsing_header::CollapsingState;
 use egui::{Color32, RichText, Ui};
 use passivate_configuration::configuration::ConfigurationChange;
+use passivate_coverage::coverage_diff::{CoverageDelta, CoverageDiff};
 use passivate_coverage::coverage_status::CoverageStatus;
 use passivate_coverage::grcov::covdir_json::CovdirJson;
 
-pub struct CoverageView;
+use crate::source_view::SourceView;
+
+pub struct CoverageView
+{
+    source_root: Utf8PathBuf,
+    source_view: Option<SourceView>
+}
 
 impl CoverageView
 {
-    pub fn ui(&mut self, ui: &mut Ui, status: &CoverageStatus) -> Option<ConfigurationChange>
+    pub fn new(source_root: Utf8PathBuf) -> Self
+    {
+        Self {
+            source_root,
+            source_view: None
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, status: &CoverageStatus, diff: Option<&CoverageDiff>) -> Option<ConfigurationChange>
     {
         match status
         {
@@ -31,20 +49,67 @@ impl CoverageView
             }
             CoverageStatus::Done(json) =>
             {
-                let egui_id = egui::Id::new(format!("root{}", json.name));
-                Self::draw_coverage(ui, json, egui_id);
+                self.draw_done(ui, json, diff);
                 None
             }
+            CoverageStatus::Cancelled =>
+            {
+                ui.heading("Cancelled");
+                None
+            }
+        }
+    }
+
+    fn draw_done(&mut self, ui: &mut Ui, json: &CovdirJson, diff: Option<&CoverageDiff>)
+    {
+        if let Some(source_view) = &self.source_view
+        {
+            if ui.button("Back").clicked()
+            {
+                self.source_view = None;
+                return;
+            }
+
+            source_view.ui(ui, json.find(source_view.path()));
+            return;
+        }
+
+        let mut selected_file = None;
+        let egui_id = egui::Id::new(format!("root{}", json.name));
+        Self::draw_coverage(ui, json, Utf8Path::new(""), egui_id, diff, &mut selected_file);
+
+        if let Some(selected_file) = selected_file
+        {
+            self.source_view = Some(SourceView::open(&self.source_root, selected_file));
         }
     }
 
-    fn draw_coverage(ui: &mut Ui, coverage: &CovdirJson, id: egui::Id)
+    fn draw_coverage(
+        ui: &mut Ui,
+        coverage: &CovdirJson,
+        path: &Utf8Path,
+        id: egui::Id,
+        diff: Option<&CoverageDiff>,
+        selected_file: &mut Option<Utf8PathBuf>
+    )
     {
         if coverage.children.as_ref().is_none_or(|children| children.is_empty())
         {
             ui.horizontal(|ui| {
-                ui.label(&coverage.name);
+                if coverage.coverage.is_some()
+                {
+                    if ui.link(&coverage.name).clicked()
+                    {
+                        *selected_file = Some(path.to_path_buf());
+                    }
+                }
+                else
+                {
+                    ui.label(&coverage.name);
+                }
+
                 ui.label(format!("{}%", &coverage.coverage_percent));
+                Self::draw_delta(ui, diff.and_then(|diff| diff.get(path)));
             });
         }
         else
@@ -54,6 +119,7 @@ impl CoverageView
                 .show_header(ui, |ui| {
                     ui.label(&coverage.name);
                     ui.label(format!("{}%", &coverage.coverage_percent));
+                    Self::draw_delta(ui, diff.and_then(|diff| diff.get(path)));
                 })
                 .body(|ui| {
                     if let Some(children) = &coverage.children
@@ -61,7 +127,7 @@ impl CoverageView
                         for child in children.values()
                         {
                             let hierarchical_id = egui::Id::new(format!("{:?}{}", id, child.name));
-                            Self::draw_coverage(ui, child, hierarchical_id);
+                            Self::draw_coverage(ui, child, &path.join(&child.name), hierarchical_id, diff, selected_file);
                         }
                     }
                     else
@@ -72,6 +138,35 @@ impl CoverageView
         }
     }
 
+    fn draw_delta(ui: &mut Ui, delta: Option<&CoverageDelta>)
+    {
+        let Some(delta) = delta
+        else
+        {
+            return;
+        };
+
+        let (marker, color) = if delta.is_regression()
+        {
+            ("⬇", Color32::RED)
+        }
+        else if delta.is_improvement()
+        {
+            ("⬆", Color32::GREEN)
+        }
+        else
+        {
+            return;
+        };
+
+        let text = RichText::new(format!("{marker} {:+.2}%", delta.percent_delta)).color(color);
+
+        ui.label(text).on_hover_text(format!(
+            "{} lines newly covered, {} lines newly uncovered",
+            delta.newly_covered, delta.newly_uncovered
+        ));
+    }
+
     fn draw_disabled(&mut self, ui: &mut Ui) -> Option<ConfigurationChange>
     {
         ui.heading("Code coverage is disabled");
@@ -90,32 +185,37 @@ impl CoverageView
 #[cfg(test)]
 mod tests
 {
+    use camino::Utf8PathBuf;
     use egui::accesskit::Role;
     use egui_kittest::{kittest::Queryable, Harness};
     use indexmap::IndexMap;
-    use passivate_coverage::{coverage_status::CoverageStatus, grcov::covdir_json::CovdirJson};
+    use passivate_coverage::{coverage_diff::CoverageDiff, coverage_status::CoverageStatus, grcov::covdir_json::CovdirJson};
     use passivate_hyp_names::test_name;
+    use passivate_testing::path_resolution::test_data_path;
 
     use crate::CoverageView;
 
     #[test]
     pub fn show_coverage_hierarchy_fully_collapsed()
     {
-        let mut coverage_view = CoverageView;
+        let mut coverage_view = CoverageView::new(Utf8PathBuf::new());
 
         let coverage_info = CovdirJson {
             children: Some(IndexMap::new()),
+            coverage: None,
             coverage_percent: 88.0,
             lines_covered: 64,
             lines_missed: 16,
             lines_total: 80,
-            name: "example.rs".to_string()
+            name: "example.rs".to_string(),
+            branches: None,
+            functions: None
         };
 
         let coverage_status = CoverageStatus::Done(Box::new(coverage_info));
 
         let ui = |ui: &mut egui::Ui| {
-            coverage_view.ui(ui, &coverage_status);
+            coverage_view.ui(ui, &coverage_status, None);
         };
 
         let mut harness = Harness::new_ui(ui);
@@ -127,7 +227,7 @@ mod tests
     #[test]
     pub fn show_coverage_hierarchy_expand_children()
     {
-        let mut coverage_view = CoverageView;
+        let mut coverage_view = CoverageView::new(Utf8PathBuf::new());
 
         let coverage_info = CovdirJson {
             children: Some(IndexMap::from([
@@ -135,11 +235,14 @@ mod tests
                     "child1.rs".to_string(),
                     CovdirJson {
                         children: None,
+                        coverage: None,
                         coverage_percent: 88.0,
                         lines_covered: 64,
                         lines_missed: 16,
                         lines_total: 80,
-                        name: "child1.rs".to_string()
+                        name: "child1.rs".to_string(),
+                        branches: None,
+                        functions: None
                     }
                 ),
                 (
@@ -150,44 +253,56 @@ mod tests
                                 "nested1.rs".to_string(),
                                 CovdirJson {
                                     children: None,
+                                    coverage: None,
                                     coverage_percent: 12.0,
                                     lines_covered: 64,
                                     lines_missed: 16,
                                     lines_total: 80,
-                                    name: "nested1.rs".to_string()
+                                    name: "nested1.rs".to_string(),
+                                    branches: None,
+                                    functions: None
                                 }
                             ),
                             (
                                 "nested2.rs".to_string(),
                                 CovdirJson {
                                     children: None,
+                                    coverage: None,
                                     coverage_percent: 24.0,
                                     lines_covered: 64,
                                     lines_missed: 16,
                                     lines_total: 80,
-                                    name: "nested2.rs".to_string()
+                                    name: "nested2.rs".to_string(),
+                                    branches: None,
+                                    functions: None
                                 }
                             )
                         ])),
+                        coverage: None,
                         coverage_percent: 100.0,
                         lines_covered: 64,
                         lines_missed: 16,
                         lines_total: 80,
-                        name: "child2.rs".to_string()
+                        name: "child2.rs".to_string(),
+                        branches: None,
+                        functions: None
                     }
                 )
             ])),
+            coverage: None,
             coverage_percent: 69.0,
             lines_covered: 64,
             lines_missed: 16,
             lines_total: 80,
-            name: "example.rs".to_string()
+            name: "example.rs".to_string(),
+            branches: None,
+            functions: None
         };
 
         let coverage_status = CoverageStatus::Done(Box::new(coverage_info));
 
         let ui = |ui: &mut egui::Ui| {
-            coverage_view.ui(ui, &coverage_status);
+            coverage_view.ui(ui, &coverage_status, None);
         };
 
         let mut harness = Harness::new_ui(ui);
@@ -219,12 +334,12 @@ mod tests
     #[test]
     pub fn show_error()
     {
-        let mut coverage_view = CoverageView;
+        let mut coverage_view = CoverageView::new(Utf8PathBuf::new());
 
         let coverage_status = CoverageStatus::Error("Something went wrong with the coverage!".to_string());
 
         let ui = |ui: &mut egui::Ui| {
-            coverage_view.ui(ui, &coverage_status);
+            coverage_view.ui(ui, &coverage_status, None);
         };
 
         let mut harness = Harness::new_ui(ui);
@@ -233,6 +348,174 @@ mod tests
         harness.snapshot(&test_name!());
     }
 
+    #[test]
+    pub fn show_cancelled()
+    {
+        let mut coverage_view = CoverageView::new(Utf8PathBuf::new());
+
+        let ui = |ui: &mut egui::Ui| {
+            coverage_view.ui(ui, &CoverageStatus::Cancelled, None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        harness.get_by_label("Cancelled");
+    }
+
+    #[test]
+    pub fn selecting_a_file_shows_its_source()
+    {
+        let mut coverage_view = CoverageView::new(test_data_path().join("simple_project"));
+
+        let coverage_status = CoverageStatus::Done(Box::new(example_source_coverage()));
+
+        let ui = |ui: &mut egui::Ui| {
+            coverage_view.ui(ui, &coverage_status, None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        expand_all(&mut harness);
+
+        harness.get_by_label("lib.rs").click();
+        harness.run();
+
+        harness.get_by_label("src/lib.rs");
+        harness.get_by_label("pub fn add(left: u64, right: u64) -> u64 {");
+    }
+
+    #[test]
+    pub fn going_back_from_source_shows_coverage_hierarchy()
+    {
+        let mut coverage_view = CoverageView::new(test_data_path().join("simple_project"));
+
+        let coverage_status = CoverageStatus::Done(Box::new(example_source_coverage()));
+
+        let ui = |ui: &mut egui::Ui| {
+            coverage_view.ui(ui, &coverage_status, None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        expand_all(&mut harness);
+
+        harness.get_by_label("lib.rs").click();
+        harness.run();
+
+        harness.get_by_label("Back").click();
+        harness.run();
+
+        harness.get_by_label("src");
+    }
+
+    #[test]
+    pub fn coverage_regression_is_marked()
+    {
+        let mut coverage_view = CoverageView::new(test_data_path().join("simple_project"));
+
+        let previous = example_source_coverage();
+        let mut current = example_source_coverage();
+        current.coverage_percent = 25.0;
+
+        let diff = CoverageDiff::between(&previous, &current);
+        let coverage_status = CoverageStatus::Done(Box::new(current));
+
+        let ui = |ui: &mut egui::Ui| {
+            coverage_view.ui(ui, &coverage_status, Some(&diff));
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        harness.get_by_label("⬇ -25.00%");
+    }
+
+    #[test]
+    pub fn coverage_improvement_is_marked()
+    {
+        let mut coverage_view = CoverageView::new(test_data_path().join("simple_project"));
+
+        let previous = example_source_coverage();
+        let mut current = example_source_coverage();
+        current.coverage_percent = 75.0;
+
+        let diff = CoverageDiff::between(&previous, &current);
+        let coverage_status = CoverageStatus::Done(Box::new(current));
+
+        let ui = |ui: &mut egui::Ui| {
+            coverage_view.ui(ui, &coverage_status, Some(&diff));
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        harness.get_by_label("⬆ +25.00%");
+    }
+
+    fn expand_all(harness: &mut Harness<'_>)
+    {
+        let top_level_header = harness.get_by_role(Role::Unknown);
+        top_level_header.click();
+
+        let top_level_header_id = top_level_header.id();
+
+        harness.run();
+
+        for header in harness.get_all_by_role(Role::Unknown)
+        {
+            if header.id() != top_level_header_id
+            {
+                header.click();
+            }
+        }
+
+        harness.run();
+    }
+
+    fn example_source_coverage() -> CovdirJson
+    {
+        CovdirJson {
+            children: Some(IndexMap::from([(
+                "src".to_string(),
+                CovdirJson {
+                    children: Some(IndexMap::from([(
+                        "lib.rs".to_string(),
+                        CovdirJson {
+                            children: None,
+                            coverage: Some(vec![-1, 1, 1, 1, -1, 0, 0, 0]),
+                            coverage_percent: 50.0,
+                            lines_covered: 3,
+                            lines_missed: 3,
+                            lines_total: 6,
+                            name: "lib.rs".to_string(),
+                            branches: None,
+                            functions: None
+                        }
+                    )])),
+                    coverage: None,
+                    coverage_percent: 50.0,
+                    lines_covered: 3,
+                    lines_missed: 3,
+                    lines_total: 6,
+                    name: "src".to_string(),
+                    branches: None,
+                    functions: None
+                }
+            )])),
+            coverage: None,
+            coverage_percent: 50.0,
+            lines_covered: 3,
+            lines_missed: 3,
+            lines_total: 6,
+            name: "".to_string(),
+            branches: None,
+            functions: None
+        }
+    }
+
     // #[test]
     // pub fn when_grcov_is_not_installed_error_is_reported() {
     //     let mut run_tests = MockRunTests::new();
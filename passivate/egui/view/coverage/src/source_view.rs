@@ -0,0 +1,84 @@
+use std::fs;
+use std::io::ErrorKind as IoErrorKind;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use egui::{Color32, RichText, ScrollArea, Ui};
+use passivate_coverage::grcov::covdir_json::CovdirJson;
+use passivate_coverage::line_coverage::LineCoverage;
+
+/// Shows a source file with each line highlighted by its coverage
+pub struct SourceView
+{
+    path: Utf8PathBuf,
+    lines: Result<Vec<String>, IoErrorKind>
+}
+
+impl SourceView
+{
+    pub fn open(source_root: &Utf8Path, path: Utf8PathBuf) -> Self
+    {
+        let lines = fs::read_to_string(source_root.join(&path))
+            .map(|source| source.lines().map(str::to_string).collect())
+            .map_err(|error| error.kind());
+
+        Self { path, lines }
+    }
+
+    pub fn path(&self) -> &Utf8Path
+    {
+        &self.path
+    }
+
+    pub fn ui(&self, ui: &mut Ui, coverage: Option<&CovdirJson>)
+    {
+        ui.heading(self.path.as_str());
+
+        let lines = match &self.lines
+        {
+            Ok(lines) => lines,
+            Err(error) =>
+            {
+                let text = RichText::new(format!("failed to read source: {error}")).color(Color32::RED);
+                ui.label(text);
+                return;
+            }
+        };
+
+        let line_coverage: Vec<LineCoverage> = coverage.map(|coverage| coverage.lines().collect()).unwrap_or_default();
+
+        ScrollArea::both().show(ui, |ui| {
+            for (index, line) in lines.iter().enumerate()
+            {
+                let coverage = line_coverage.get(index).copied().unwrap_or(LineCoverage::NotExecutable);
+
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{:>5}", index + 1)).monospace().color(Color32::GRAY));
+                    ui.label(RichText::new(hits_text(coverage)).monospace().color(Color32::GRAY));
+                    ui.label(line_text(line, coverage));
+                });
+            }
+        });
+    }
+}
+
+fn hits_text(coverage: LineCoverage) -> String
+{
+    match coverage
+    {
+        LineCoverage::Covered(hits) => format!("{hits:>6}"),
+        LineCoverage::Missed => format!("{:>6}", 0),
+        LineCoverage::NotExecutable => format!("{:>6}", "")
+    }
+}
+
+fn line_text(line: &str, coverage: LineCoverage) -> RichText
+{
+    let text = RichText::new(line).monospace();
+
+    match coverage
+    {
+        LineCoverage::NotExecutable => text,
+        LineCoverage::Missed => text.background_color(Color32::from_rgb(96, 32, 32)),
+        LineCoverage::Covered(_) => text.background_color(Color32::from_rgb(32, 80, 32))
+    }
+}
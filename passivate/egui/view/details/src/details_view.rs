@@ -1,7 +1,12 @@
-use egui::{Color32, RichText, TextureHandle, Ui};
+use std::time::Duration;
+
+use egui::{CollapsingHeader, Color32, RichText, TextureHandle, Ui};
+use passivate_egui_core::hyp_duration::{self, format_duration};
 use passivate_egui_hyp_snapshots::snapshot_error::SnapshotError;
 use passivate_egui_hyp_snapshots::snapshot_handles::SnapshotHandles;
 use passivate_model_bridge::bridge::Bridge;
+use passivate_model_bridge::bridge_output::BridgeOutput;
+use passivate_model_bridge::hyp_failure::{AssertionValues, HypFailure};
 use passivate_model_bridge::hyp_state::HypState;
 
 use crate::hyp_details::HypDetails;
@@ -10,22 +15,34 @@ pub struct DetailsView;
 
 impl DetailsView
 {
-    pub fn ui<TBridge: Bridge>(&mut self, ui: &mut Ui, details: Option<&HypDetails<'_, TBridge>>)
+    /// Returns whether the hyp was asked to run on its own
+    pub fn ui<TBridge: Bridge>(
+        &mut self,
+        ui: &mut Ui,
+        details: Option<&HypDetails<'_, TBridge>>,
+        slow_threshold: Option<Duration>
+    ) -> bool
     {
         if let Some(details) = details
         {
             let color = match details.hyp.state()
             {
                 HypState::Passed => Color32::GREEN,
-                HypState::Failed => Color32::RED,
-                HypState::Unknown => Color32::GRAY,
+                HypState::Failed | HypState::BuildFailed => Color32::RED,
+                HypState::Flaky => Color32::ORANGE,
+                HypState::TimedOut => Color32::LIGHT_RED,
+                HypState::Unknown | HypState::Skipped => Color32::GRAY,
                 HypState::Running => Color32::LIGHT_BLUE
             };
 
+            let mut run_requested = false;
+
             ui.horizontal(|ui| {
                 let text = RichText::new(format!("{}", details.hyp)).size(16.0).color(color);
                 ui.heading(text);
 
+                run_requested = ui.button("Run").clicked();
+
                 if ui.button("Pin").clicked()
                 {
                     todo!();
@@ -37,11 +54,51 @@ impl DetailsView
                 }
             });
 
+            if let Some(duration) = details.hyp.duration()
+            {
+                let mut text = format!("Took {}", format_duration(duration));
+
+                if hyp_duration::is_slow(duration, slow_threshold)
+                {
+                    text.push_str(", which is slow");
+                }
+
+                ui.label(RichText::new(text).size(12.0).color(Color32::GRAY));
+            }
+
+            if details.hyp.state() == HypState::Skipped
+            {
+                ui.label(
+                    RichText::new("Ignored, so it only runs on demand")
+                        .size(12.0)
+                        .color(Color32::GRAY)
+                );
+            }
+
+            if details.hyp.state() == HypState::TimedOut
+            {
+                let text = "Terminated for running too long, so its output is only what it printed until then";
+                ui.label(RichText::new(text).size(12.0).color(Color32::LIGHT_RED));
+            }
+
+            if details.hyp.flaky_runs() > 0
+            {
+                let text = format!("Only passed after a retry in {} runs", details.hyp.flaky_runs());
+                ui.label(RichText::new(text).size(12.0).color(Color32::ORANGE));
+            }
+
+            if let Some(failure) = details.hyp.iter_output().find_map(BridgeOutput::failure)
+            {
+                ui.add_space(16.0);
+                Self::draw_failure(ui, failure);
+            }
+
             if details.hyp.has_output()
             {
                 ui.add_space(16.0);
 
-                for output in details.hyp.iter_output()
+                // The failure is drawn above, rather than as another line of output
+                for output in details.hyp.iter_output().filter(|output| output.failure().is_none())
                 {
                     let output_line = RichText::new(output.to_string()).size(12.0).color(color);
                     ui.label(output_line);
@@ -52,10 +109,63 @@ impl DetailsView
             {
                 self.draw_snapshots::<TBridge>(ui, snapshot_handles);
             }
+
+            run_requested
         }
         else
         {
             ui.heading("No test selected");
+            false
+        }
+    }
+
+    fn draw_failure(ui: &mut Ui, failure: &HypFailure)
+    {
+        if let Some(location) = &failure.location
+        {
+            ui.label(
+                RichText::new(location.to_string())
+                    .size(12.0)
+                    .monospace()
+                    .color(Color32::GRAY)
+            );
+        }
+
+        ui.label(RichText::new(&failure.message).size(14.0).color(Color32::RED));
+
+        if let Some(assertion) = &failure.assertion
+        {
+            Self::draw_assertion_diff(ui, assertion);
+        }
+
+        if !failure.backtrace.is_empty()
+        {
+            CollapsingHeader::new("Backtrace").default_open(false).show(ui, |ui| {
+                ui.label(RichText::new(failure.backtrace.join("\n")).size(12.0).monospace());
+            });
+        }
+    }
+
+    /// Lines the values share are shown once, lines that differ as removed from left and added by right
+    fn draw_assertion_diff(ui: &mut Ui, assertion: &AssertionValues)
+    {
+        let mut left = assertion.left.lines();
+        let mut right = assertion.right.lines();
+
+        loop
+        {
+            match (left.next(), right.next())
+            {
+                (Some(left), Some(right)) if left == right => diff_line(ui, ' ', left, Color32::GRAY),
+                (Some(left), Some(right)) =>
+                {
+                    diff_line(ui, '-', left, Color32::RED);
+                    diff_line(ui, '+', right, Color32::GREEN);
+                }
+                (Some(left), None) => diff_line(ui, '-', left, Color32::RED),
+                (None, Some(right)) => diff_line(ui, '+', right, Color32::GREEN),
+                (None, None) => break
+            }
         }
     }
 
@@ -110,19 +220,29 @@ impl DetailsView
     }
 }
 
+fn diff_line(ui: &mut Ui, sign: char, line: &str, color: Color32)
+{
+    ui.label(RichText::new(format!("{sign} {line}")).size(12.0).monospace().color(color));
+}
+
 #[cfg(test)]
 mod tests
 {
+    use std::time::Duration;
+
     use camino::Utf8PathBuf;
     use egui_kittest::Harness;
+    use egui_kittest::kittest::Queryable;
     use passivate_egui_hyp_snapshots::Snapshots;
     use passivate_egui_hyp_snapshots::snapshot_handles::SnapshotHandles;
     use passivate_hyp_names::hyp_id::HypId;
     use passivate_hyp_names::test_name;
     use passivate_model_bridge::bridge::Bridge;
+    use passivate_model_bridge::hyp_failure::{AssertionValues, FailureLocation, HypFailure};
+    use passivate_model_bridge::hyp_session_event::ConsoleOutput;
     use passivate_model_bridge::hyp_state::HypState;
     use passivate_model_core::hyp::Hyp;
-    use passivate_run_rust::model::{RustBridge, RustHyp};
+    use passivate_run_rust::model::{RustBridge, RustHyp, RustOutput};
     use passivate_testing::model::{TestHyp, TestHypKind, TestOutput, TestSession};
     use passivate_testing::path_resolution::test_data_path;
     use rstest::*;
@@ -160,6 +280,83 @@ mod tests
         show_hyp(&test_name!(), failing_test);
     }
 
+    #[test]
+    pub fn show_how_long_a_test_took()
+    {
+        let passing_test =
+            example_hyp("example_crate::example_test", HypState::Passed).with_duration(Some(Duration::from_millis(12)));
+
+        show_hyp(&test_name!(), passing_test);
+    }
+
+    #[test]
+    pub fn show_a_skipped_test()
+    {
+        let skipped_test = example_hyp("example_crate::example_test", HypState::Skipped);
+
+        show_hyp(&test_name!(), skipped_test);
+    }
+
+    #[test]
+    pub fn show_a_timed_out_test_with_its_partial_output()
+    {
+        let mut timed_out_test = example_hyp("example_crate::example_test", HypState::TimedOut);
+
+        timed_out_test.add_output(RustOutput::Console(ConsoleOutput::new_stdout("waiting for a reply")));
+
+        show_hyp(&test_name!(), timed_out_test);
+    }
+
+    #[test]
+    pub fn clicking_run_asks_for_the_test_to_run()
+    {
+        let mut details_view = DetailsView;
+
+        let skipped_test = example_hyp("example_crate::example_test", HypState::Skipped);
+        let mut run_requested = false;
+
+        let ui = |ui: &mut egui::Ui| {
+            let details = HypDetails {
+                hyp: &skipped_test,
+                snapshot_handles: None
+            };
+
+            run_requested |= details_view.ui(ui, Some(&details), None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        harness.get_by_label("Run").click();
+        harness.run();
+
+        drop(harness);
+
+        assert!(run_requested);
+    }
+
+    #[test]
+    pub fn show_location_message_and_diff_of_a_failed_assertion()
+    {
+        let mut failing_test = example_hyp("example_crate::example_test", HypState::Failed);
+
+        failing_test.add_output(RustOutput::Failure(HypFailure {
+            message: String::from("assertion `left == right` failed"),
+            location: Some(FailureLocation {
+                file: Utf8PathBuf::from("tests/multiply_tests.rs"),
+                line: 6,
+                column: 5
+            }),
+            assertion: Some(AssertionValues {
+                left: String::from("[\n    1,\n    5,\n]"),
+                right: String::from("[\n    1,\n    4,\n]")
+            }),
+            backtrace: vec![String::from("0: rust_begin_unwind")]
+        }));
+
+        show_hyp(&test_name!(), failing_test);
+    }
+
     #[test]
     pub fn show_snapshot_associated_with_test_rgb()
     {
@@ -220,7 +417,7 @@ mod tests
                 snapshot_handles: Some(&snapshot_handles)
             };
 
-            details_view.ui(ui, Some(&details));
+            details_view.ui(ui, Some(&details), None);
         };
 
         let mut harness = Harness::new_ui(ui);
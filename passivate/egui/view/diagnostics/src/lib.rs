@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8Path;
+use egui::collapsing_header::CollapsingState;
+use egui::{Color32, RichText, ScrollArea, Ui};
+use passivate_model_bridge::compiler_diagnostic::{CompilerDiagnostic, DiagnosticLevel, DiagnosticSpan};
+
+pub struct DiagnosticsView;
+
+impl DiagnosticsView
+{
+    pub fn ui<'a>(&mut self, ui: &mut Ui, diagnostics: impl IntoIterator<Item = &'a CompilerDiagnostic>)
+    {
+        let mut diagnostics_by_file: BTreeMap<Option<&Utf8Path>, Vec<&CompilerDiagnostic>> = BTreeMap::new();
+
+        for diagnostic in diagnostics
+        {
+            diagnostics_by_file.entry(diagnostic.file()).or_default().push(diagnostic);
+        }
+
+        if diagnostics_by_file.is_empty()
+        {
+            ui.heading("No diagnostics");
+            return;
+        }
+
+        ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
+            for (file, diagnostics) in diagnostics_by_file
+            {
+                // Diagnostics without a file are about the build as a whole, such as the number of errors
+                let file = file.map_or("Build", Utf8Path::as_str);
+
+                ui.heading(RichText::new(file).size(16.0));
+
+                for (index, diagnostic) in diagnostics.into_iter().enumerate()
+                {
+                    Self::draw_diagnostic(ui, diagnostic, egui::Id::new((file, index)));
+                }
+            }
+        });
+    }
+
+    fn draw_diagnostic(ui: &mut Ui, diagnostic: &CompilerDiagnostic, id: egui::Id)
+    {
+        let default_open = false;
+        CollapsingState::load_with_default_open(ui.ctx(), id, default_open)
+            .show_header(ui, |ui| {
+                ui.label(RichText::new(diagnostic.to_string()).color(level_color(diagnostic.level)));
+
+                if let Some(span) = diagnostic.primary_span()
+                {
+                    ui.label(format!("{}:{}", span.line_start, span.column_start));
+                }
+            })
+            .body(|ui| {
+                if let Some(label) = diagnostic.primary_span().and_then(|span| span.label.as_ref())
+                {
+                    ui.label(label);
+                }
+
+                for span in diagnostic.secondary_spans()
+                {
+                    ui.label(RichText::new(span_text(span)).color(Color32::GRAY));
+                }
+
+                for child in &diagnostic.children
+                {
+                    ui.label(RichText::new(child.to_string()).color(level_color(child.level)));
+                }
+
+                for span in diagnostic.suggested_replacements()
+                {
+                    if let Some(replacement) = &span.suggested_replacement
+                    {
+                        let suggestion = format!("{}: replace with `{replacement}`", location(span));
+
+                        ui.label(RichText::new(suggestion).color(Color32::GREEN));
+                    }
+                }
+
+                if let Some(rendered) = &diagnostic.rendered
+                {
+                    ui.label(RichText::new(rendered).monospace());
+                }
+            });
+    }
+}
+
+fn location(span: &DiagnosticSpan) -> String
+{
+    format!("{}:{}:{}", span.file, span.line_start, span.column_start)
+}
+
+/// Where a span is, with what the compiler says about it
+fn span_text(span: &DiagnosticSpan) -> String
+{
+    match &span.label
+    {
+        Some(label) => format!("{}: {label}", location(span)),
+        None => location(span)
+    }
+}
+
+fn level_color(level: DiagnosticLevel) -> Color32
+{
+    match level
+    {
+        DiagnosticLevel::Error => Color32::RED,
+        DiagnosticLevel::Warning => Color32::YELLOW,
+        DiagnosticLevel::Note | DiagnosticLevel::Help => Color32::GRAY
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::Utf8PathBuf;
+    use egui::accesskit::Role;
+    use egui_kittest::Harness;
+    use egui_kittest::kittest::Queryable;
+    use passivate_hyp_names::test_name;
+    use passivate_model_bridge::compiler_diagnostic::{CompilerDiagnostic, DiagnosticLevel, DiagnosticSpan};
+
+    use crate::DiagnosticsView;
+
+    #[test]
+    pub fn show_diagnostics_grouped_by_file()
+    {
+        let mut diagnostics_view = DiagnosticsView;
+
+        let diagnostics = [
+            example_diagnostic("src/lib.rs", DiagnosticLevel::Error, "mismatched types"),
+            example_diagnostic("src/main.rs", DiagnosticLevel::Warning, "unused variable: `x`"),
+            example_diagnostic("src/lib.rs", DiagnosticLevel::Warning, "unused import: `std::fs`")
+        ];
+
+        let ui = |ui: &mut egui::Ui| {
+            diagnostics_view.ui(ui, &diagnostics);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+        harness.fit_contents();
+        harness.snapshot(&test_name!());
+    }
+
+    #[test]
+    pub fn expanding_a_diagnostic_shows_its_rendered_text()
+    {
+        let mut diagnostics_view = DiagnosticsView;
+
+        let diagnostics = [example_diagnostic("src/lib.rs", DiagnosticLevel::Error, "mismatched types")];
+        let rendered = diagnostics[0].rendered.clone().unwrap();
+
+        let ui = |ui: &mut egui::Ui| {
+            diagnostics_view.ui(ui, &diagnostics);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        assert!(harness.query_by_label(&rendered).is_none());
+
+        harness.get_by_role(Role::Unknown).click();
+        harness.run();
+
+        harness.get_by_label(&rendered);
+    }
+
+    #[test]
+    pub fn show_labels_children_and_suggestions_of_an_expanded_diagnostic()
+    {
+        let mut diagnostics_view = DiagnosticsView;
+
+        let mut diagnostic = example_diagnostic("src/lib.rs", DiagnosticLevel::Error, "mismatched types");
+        diagnostic.rendered = None;
+        diagnostic.spans[0].label = Some("expected `u64`, found `&str`".to_string());
+        diagnostic.spans.push(DiagnosticSpan {
+            line_start: 1,
+            line_end: 1,
+            column_start: 8,
+            column_end: 11,
+            is_primary: false,
+            label: Some("arguments to this function are incorrect".to_string()),
+            ..diagnostic.spans[0].clone()
+        });
+        diagnostic.children.push(CompilerDiagnostic {
+            message: "try using a conversion method".to_string(),
+            code: None,
+            level: DiagnosticLevel::Help,
+            spans: vec![DiagnosticSpan {
+                is_primary: true,
+                label: None,
+                suggested_replacement: Some("\"2\".parse().unwrap()".to_string()),
+                ..diagnostic.spans[0].clone()
+            }],
+            children: Vec::new(),
+            rendered: None
+        });
+
+        let diagnostics = [diagnostic];
+
+        let ui = |ui: &mut egui::Ui| {
+            diagnostics_view.ui(ui, &diagnostics);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        harness.get_by_role(Role::Unknown).click();
+        harness.run();
+
+        harness.get_by_label("expected `u64`, found `&str`");
+        harness.get_by_label("src/lib.rs:1:8: arguments to this function are incorrect");
+        harness.get_by_label("help: try using a conversion method");
+        harness.get_by_label("src/lib.rs:3:5: replace with `\"2\".parse().unwrap()`");
+
+        harness.fit_contents();
+        harness.snapshot(&test_name!());
+    }
+
+    fn example_diagnostic(file: &str, level: DiagnosticLevel, message: &str) -> CompilerDiagnostic
+    {
+        CompilerDiagnostic {
+            message: message.to_string(),
+            code: None,
+            level,
+            spans: vec![DiagnosticSpan {
+                file: Utf8PathBuf::from(file),
+                line_start: 3,
+                line_end: 3,
+                column_start: 5,
+                column_end: 10,
+                is_primary: true,
+                label: None,
+                suggested_replacement: None
+            }],
+            children: Vec::new(),
+            rendered: Some(format!("{level}: {message}\n --> {file}:3:5"))
+        }
+    }
+}
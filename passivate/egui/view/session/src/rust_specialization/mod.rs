@@ -1,25 +1,88 @@
-use egui::Ui;
+use std::time::Duration;
+
+use egui::{Color32, RichText, Ui};
+use passivate_model_bridge::hyp_session_event::{CompilationMessage, CompilationMessageKind};
 use passivate_model_core::hyp::Hyp;
-use passivate_run_rust::model::{RustBridge, RustHypKind};
+use passivate_model_core::hyp_session::HypSession;
+use passivate_run_rust::model::{RustBridge, RustHypKind, RustOutput, WorkspaceCompilation};
 
 use crate::session_view::{self, HypUiAction};
-use crate::specialize_session_ui::SpecializeSessionUi;
+use crate::specialize_session_ui::{SpecializeOutputUi, SpecializeSessionUi};
 
 impl SpecializeSessionUi for Hyp<RustBridge>
 {
-    fn ui(&self, ui: &mut Ui) -> Option<HypUiAction>
+    fn ui(&self, ui: &mut Ui, slow_threshold: Option<Duration>) -> Option<HypUiAction>
     {
         let info = self.info();
         let name = info.name();
 
         match &info.kind
         {
-            RustHypKind::Single(_) => session_view::show_hyp(ui, self, name),
+            RustHypKind::Single(_) => session_view::show_hyp(ui, self, name, slow_threshold),
             RustHypKind::Package(package_info) =>
             {
                 let package = format!("{} ({})", name, package_info.manifest_path);
-                session_view::show_hyp(ui, self, package)
+                session_view::show_hyp(ui, self, package, slow_threshold)
             }
         }
     }
 }
+
+impl SpecializeOutputUi for HypSession<RustBridge>
+{
+    fn output_ui(&self, ui: &mut Ui)
+    {
+        // Only the latest progress of the build is of interest, whereas every diagnostic remains relevant
+        match self.iter_output().filter(|output| is_build_progress(output)).last()
+        {
+            Some(RustOutput::Workspace(WorkspaceCompilation::WaitForLock)) =>
+            {
+                ui.label(RichText::new("Waiting for file lock").size(12.0).color(Color32::GRAY));
+            }
+            Some(RustOutput::Workspace(WorkspaceCompilation::Message(progress))) =>
+            {
+                session_view::compilation_label(ui, progress);
+            }
+            _ =>
+            {}
+        }
+
+        for output in self.iter_output()
+        {
+            match output
+            {
+                RustOutput::Workspace(WorkspaceCompilation::Message(message)) | RustOutput::Project(message)
+                    if !is_info(message) =>
+                {
+                    session_view::compilation_label(ui, message);
+                }
+                // Errors that failed the build are already listed with the session state
+                RustOutput::Diagnostic(diagnostic) if !self.build_errors().contains(diagnostic) =>
+                {
+                    session_view::diagnostic_label(ui, diagnostic);
+                }
+                _ =>
+                {}
+            }
+        }
+    }
+}
+
+fn is_build_progress(output: &RustOutput) -> bool
+{
+    match output
+    {
+        RustOutput::Workspace(WorkspaceCompilation::WaitForLock) => true,
+        RustOutput::Workspace(WorkspaceCompilation::Message(message)) => is_info(message),
+        RustOutput::Project(_)
+        | RustOutput::Diagnostic(_)
+        | RustOutput::Console(_)
+        | RustOutput::StartError(_)
+        | RustOutput::Failure(_) => false
+    }
+}
+
+fn is_info(message: &CompilationMessage) -> bool
+{
+    matches!(message.kind, CompilationMessageKind::Info)
+}
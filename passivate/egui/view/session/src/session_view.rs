@@ -1,13 +1,24 @@
+use std::cmp::Reverse;
+use std::time::Duration;
+
 use egui::{Color32, RichText, Ui};
+use passivate_egui_core::hyp_duration::{self, format_duration};
 use passivate_model_bridge::bridge::Bridge;
+use passivate_model_bridge::compiler_diagnostic::{CompilerDiagnostic, DiagnosticLevel};
+use passivate_model_bridge::hyp_session_event::{CompilationMessage, CompilationMessageKind};
 use passivate_model_bridge::hyp_state::HypState;
+use passivate_model_bridge::run_progress::{RunProgress, RunSummary};
 use passivate_model_core::hyp::Hyp;
 use passivate_model_core::hyp_session::HypSession;
 use passivate_model_core::hyp_session_state_error::HypSessionStateError;
 
-use crate::specialize_session_ui::SpecializeSessionUi;
+use crate::specialize_session_ui::{SpecializeOutputUi, SpecializeSessionUi};
 
-pub struct SessionView;
+#[derive(Default)]
+pub struct SessionView
+{
+    sort_by_duration: bool
+}
 
 pub(crate) enum HypUiAction
 {
@@ -16,7 +27,17 @@ pub(crate) enum HypUiAction
 
 impl SessionView
 {
-    pub fn ui<'a, TBridge: Bridge>(&mut self, ui: &mut Ui, session: &'a HypSession<TBridge>) -> Option<&'a Hyp<TBridge>>
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn ui<'a, TBridge: Bridge>(
+        &mut self,
+        ui: &mut Ui,
+        session: &'a HypSession<TBridge>,
+        slow_threshold: Option<Duration>
+    ) -> Option<&'a Hyp<TBridge>>
     {
         match session.activity()
         {
@@ -24,11 +45,44 @@ impl SessionView
             Err(error) => self.show_error_state(ui, error)
         }
 
+        if let Some(failure_reason) = session.failure_reason()
+        {
+            ui.label(RichText::new(failure_reason).color(Color32::RED));
+        }
+
+        for build_error in session.build_errors()
+        {
+            diagnostic_label(ui, build_error);
+        }
+
+        if let Ok(HypState::Running) = session.activity()
+            && let Some(progress) = session.progress()
+        {
+            progress_ui(ui, progress);
+        }
+
+        if let Some(summary) = session.summary()
+        {
+            summary_label(ui, summary);
+        }
+
+        session.output_ui(ui);
+
+        ui.toggle_value(&mut self.sort_by_duration, "Sort by duration");
+
+        let mut hyps: Vec<&Hyp<TBridge>> = session.hyps().iter().collect();
+
+        if self.sort_by_duration
+        {
+            // Slowest first, hyps that have not finished last
+            hyps.sort_by_key(|hyp| Reverse(hyp.duration()));
+        }
+
         let mut selected_hyp = None;
 
-        for hyp in session.hyps().iter()
+        for hyp in hyps
         {
-            match hyp.ui(ui)
+            match hyp.ui(ui, slow_threshold)
             {
                 Some(HypUiAction::Selected) => selected_hyp = Some(hyp),
                 None => ()
@@ -45,7 +99,11 @@ impl SessionView
             HypState::Unknown => RichText::new("Idle").size(16.0).color(Color32::GREEN),
             HypState::Running => RichText::new("Running").size(16.0).color(Color32::GREEN),
             HypState::Failed => RichText::new("Failed").size(16.0).color(Color32::RED),
-            HypState::Passed => RichText::new("Passed").size(16.0).color(Color32::GREEN)
+            HypState::Passed => RichText::new("Passed").size(16.0).color(Color32::GREEN),
+            HypState::Flaky => RichText::new("Flaky").size(16.0).color(Color32::ORANGE),
+            HypState::Skipped => RichText::new("Skipped").size(16.0).color(Color32::GRAY),
+            HypState::TimedOut => RichText::new("Timed out").size(16.0).color(Color32::LIGHT_RED),
+            HypState::BuildFailed => RichText::new("Build failed").size(16.0).color(Color32::RED)
         };
 
         ui.label(text);
@@ -78,29 +136,167 @@ pub(crate) fn hyp_label(ui: &mut Ui, text: impl Into<String>)
     ui.label(text);
 }
 
-pub(crate) fn show_hyp<TBridge: Bridge>(ui: &mut Ui, hyp: &Hyp<TBridge>, text: impl Into<String>) -> Option<HypUiAction>
+pub(crate) fn compilation_label(ui: &mut Ui, message: &CompilationMessage)
+{
+    let color = match message.kind
+    {
+        CompilationMessageKind::Info => Color32::GRAY,
+        CompilationMessageKind::Warning => Color32::YELLOW,
+        CompilationMessageKind::Error => Color32::RED
+    };
+
+    ui.label(RichText::new(&message.content).size(12.0).color(color));
+}
+
+/// The headline of a diagnostic, its details are left to the diagnostics view
+pub(crate) fn diagnostic_label(ui: &mut Ui, diagnostic: &CompilerDiagnostic)
 {
-    match hyp.state()
+    let color = match diagnostic.level
     {
-        HypState::Failed => hyp_button(ui, text, Color32::RED),
-        HypState::Passed => hyp_button(ui, text, Color32::GREEN),
+        DiagnosticLevel::Error => Color32::RED,
+        DiagnosticLevel::Warning => Color32::YELLOW,
+        DiagnosticLevel::Note | DiagnosticLevel::Help => Color32::GRAY
+    };
+
+    ui.label(RichText::new(diagnostic.to_string()).size(12.0).color(color));
+}
+
+pub(crate) fn show_hyp<TBridge: Bridge>(
+    ui: &mut Ui,
+    hyp: &Hyp<TBridge>,
+    text: impl Into<String>,
+    slow_threshold: Option<Duration>
+) -> Option<HypUiAction>
+{
+    ui.horizontal(|ui| {
+        let action = show_hyp_state(ui, hyp, text);
+
+        if let Some(duration) = hyp.duration()
+        {
+            duration_label(ui, duration, slow_threshold);
+        }
+
+        if hyp.flaky_runs() > 0
+        {
+            flaky_runs_label(ui, hyp.flaky_runs());
+        }
+
+        action
+    })
+    .inner
+}
+
+fn show_hyp_state<TBridge: Bridge>(ui: &mut Ui, hyp: &Hyp<TBridge>, text: impl Into<String>) -> Option<HypUiAction>
+{
+    let color = match hyp.state()
+    {
+        HypState::Failed | HypState::BuildFailed => Color32::RED,
+        HypState::Passed => Color32::GREEN,
+        HypState::Flaky => Color32::ORANGE,
+        HypState::TimedOut => Color32::LIGHT_RED,
+        // Unlike a hyp of unknown state a skipped hyp can be selected, to run it on demand
+        HypState::Skipped => Color32::GRAY,
         HypState::Unknown =>
         {
             hyp_label(ui, text);
-            None
+            return None;
         }
-        HypState::Running => hyp_button(ui, text, Color32::LIGHT_BLUE)
+        HypState::Running => Color32::LIGHT_BLUE
+    };
+
+    // A stale hyp shows what an earlier run reported, so it is dimmed
+    let color = if hyp.is_stale() { color.gamma_multiply(0.5) } else { color };
+
+    hyp_button(ui, text, color)
+}
+
+fn duration_label(ui: &mut Ui, duration: Duration, slow_threshold: Option<Duration>)
+{
+    let text = format_duration(duration);
+
+    if hyp_duration::is_slow(duration, slow_threshold)
+    {
+        ui.label(RichText::new(format!("{text} (slow)")).size(12.0).color(Color32::YELLOW));
+    }
+    else
+    {
+        ui.label(RichText::new(text).size(12.0).color(Color32::GRAY));
     }
 }
 
+fn progress_ui(ui: &mut Ui, progress: &RunProgress)
+{
+    let mut text = format!("{} / {}", progress.finished(), progress.total);
+
+    if let Some(eta) = progress.eta()
+    {
+        text.push_str(&format!(", about {} left", format_duration(eta)));
+    }
+
+    ui.add(egui::ProgressBar::new(progress.fraction()).text(text));
+
+    let counts = format!(
+        "{} passed, {} failed, {} skipped, {} remaining",
+        progress.passed,
+        progress.failed,
+        progress.skipped,
+        progress.remaining()
+    );
+
+    ui.label(RichText::new(counts).size(12.0).color(Color32::GRAY));
+}
+
+fn summary_label(ui: &mut Ui, summary: &RunSummary)
+{
+    let progress = &summary.progress;
+    let mut counts = format!("{} passed, {} failed", progress.passed, progress.failed);
+
+    if progress.skipped > 0
+    {
+        counts.push_str(&format!(", {} skipped", progress.skipped));
+    }
+
+    let text = format!("{counts} in {}", format_duration(summary.elapsed));
+    let color = if progress.failed > 0 { Color32::RED } else { Color32::GREEN };
+
+    ui.label(RichText::new(text).size(12.0).color(color));
+}
+
+fn flaky_runs_label(ui: &mut Ui, flaky_runs: u32)
+{
+    let text = if flaky_runs == 1
+    {
+        String::from("flaky once")
+    }
+    else
+    {
+        format!("flaky {flaky_runs} times")
+    };
+
+    ui.label(RichText::new(text).size(12.0).color(Color32::ORANGE));
+}
+
 #[cfg(test)]
 mod tests
 {
+    use std::time::Duration;
+
+    use camino::Utf8PathBuf;
+    use egui::accesskit::Role;
     use egui_kittest::Harness;
+    use egui_kittest::kittest::Queryable;
+    use passivate_hyp_names::hyp_id::HypId;
     use passivate_hyp_names::test_name;
-    use passivate_model_bridge::hyp_session_event::HypSessionEvent;
+    use passivate_model_bridge::compiler_diagnostic::{CompilerDiagnostic, DiagnosticLevel, DiagnosticSpan};
+    use passivate_model_bridge::hyp_report::HypReport;
+    use passivate_model_bridge::hyp_session_event::{CompilationMessage, HypSessionEvent};
+    use passivate_model_bridge::hyp_state::HypState;
+    use passivate_model_bridge::output_report::OutputReport;
+    use passivate_model_bridge::run_extent::RunExtent;
+    use passivate_model_bridge::run_progress::{RunProgress, RunSummary};
     use passivate_model_core::hyp_session::HypSession;
-    use passivate_run_rust::model::RustBridge;
+    use passivate_run_rust::hyp_run_error::HypRunError;
+    use passivate_run_rust::model::{RustBridge, RustHyp, RustOutput, WorkspaceCompilation};
 
     use crate::session_view::SessionView;
 
@@ -110,7 +306,10 @@ mod tests
         let mut session = HypSession::new();
 
         // Start the session twice to trigger an error
-        session.update_all([HypSessionEvent::RunStarted, HypSessionEvent::RunStarted]);
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::RunStarted(RunExtent::AllHyps)
+        ]);
 
         run_and_snapshot(session, test_name!());
     }
@@ -120,11 +319,106 @@ mod tests
     {
         let mut session = HypSession::new();
 
-        session.update(HypSessionEvent::RunStarted);
+        session.update(HypSessionEvent::RunStarted(RunExtent::AllHyps));
 
         run_and_snapshot(session, test_name!());
     }
 
+    #[test]
+    pub fn show_reason_when_hyp_run_fails()
+    {
+        let mut session: HypSession<RustBridge> = HypSession::new();
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::RunError(HypRunError::Io(String::from("the disk is full")))
+        ]);
+
+        let mut test_run_view = SessionView::new();
+
+        let ui = move |ui: &mut egui::Ui| {
+            _ = test_run_view.ui(ui, &session, None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+        harness.run();
+
+        harness.get_by_label("the disk is full");
+    }
+
+    #[test]
+    pub fn show_results_of_previous_run_dimmed_while_a_new_run_is_running()
+    {
+        let mut session = HypSession::new();
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Passed
+            )),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_3_is_5")),
+                HypState::Failed
+            )),
+            HypSessionEvent::RunCompleted,
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Passed
+            ))
+        ]);
+
+        run_and_snapshot(session, test_name!());
+    }
+
+    #[test]
+    pub fn show_durations_and_flag_slow_hyps()
+    {
+        let mut test_run_view = SessionView::new();
+        let session = session_with_durations();
+
+        let ui = move |ui: &mut egui::Ui| {
+            _ = test_run_view.ui(ui, &session, Some(Duration::from_secs(1)));
+        };
+
+        let mut harness = Harness::new_ui(ui);
+
+        harness.run();
+        harness.get_by_label("1.50 s (slow)");
+        harness.get_by_label("12 ms");
+        harness.fit_contents();
+        harness.snapshot(&test_name!());
+    }
+
+    #[test]
+    pub fn show_slowest_hyps_first_when_sorted_by_duration()
+    {
+        let mut test_run_view = SessionView::new();
+        let session = session_with_durations();
+
+        let ui = move |ui: &mut egui::Ui| {
+            _ = test_run_view.ui(ui, &session, None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+
+        harness.run();
+        harness.get_by_label("Sort by duration").click();
+        harness.run();
+
+        let hyp_labels: Vec<String> = harness
+            .get_all_by_role(Role::Button)
+            .filter_map(|node| node.label())
+            .filter(|label| label.ends_with("_2_and_2_is_4"))
+            .collect();
+
+        assert_eq!(vec!["multiply_2_and_2_is_4", "add_2_and_2_is_4"], hyp_labels);
+
+        harness.fit_contents();
+        harness.snapshot(&test_name!());
+    }
+
     #[test]
     pub fn show_when_no_tests_were_found()
     {
@@ -134,27 +428,236 @@ mod tests
     #[test]
     pub fn show_when_build_failed()
     {
-        todo!();
+        let mut session = HypSession::new();
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Passed
+            )),
+            HypSessionEvent::RunCompleted,
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::BuildFailed(vec![CompilerDiagnostic {
+                message: String::from("mismatched types"),
+                code: Some(String::from("E0308")),
+                level: DiagnosticLevel::Error,
+                spans: vec![DiagnosticSpan {
+                    file: Utf8PathBuf::from("src/lib.rs"),
+                    line_start: 6,
+                    line_end: 6,
+                    column_start: 5,
+                    column_end: 10,
+                    is_primary: true,
+                    label: Some(String::from("expected `String`, found integer")),
+                    suggested_replacement: None
+                }],
+                children: Vec::new(),
+                rendered: None
+            }])
+        ]);
+
+        run_and_snapshot(session, test_name!());
     }
 
     #[test]
     pub fn show_tests_with_unknown_status_greyed_out()
     {
-        todo!();
+        let mut session = HypSession::new();
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Hyp(HypReport::new_derived(RustHyp::new_single(HypId::new(
+                "simple_project",
+                "simple_project",
+                "add_2_and_2_is_4"
+            )))),
+            HypSessionEvent::Hyp(HypReport::new_derived(RustHyp::new_single(HypId::new(
+                "simple_project",
+                "simple_project",
+                "add_2_and_3_is_5"
+            )))),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Passed
+            ))
+        ]);
+
+        run_and_snapshot(session, test_name!());
     }
 
     #[test]
     pub fn show_build_status_above_tests_while_compiling()
     {
-        todo!();
+        let mut session = HypSession::new();
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Output(OutputReport::new(
+                HypId::empty(),
+                RustOutput::Workspace(WorkspaceCompilation::Message(CompilationMessage::new_info(
+                    "Compiling simple_project v0.1.0"
+                )))
+            )),
+            HypSessionEvent::Output(OutputReport::new(
+                HypId::empty(),
+                RustOutput::Project(CompilationMessage::new_warning("warning: unused variable: `x`"))
+            )),
+            HypSessionEvent::Output(OutputReport::new(
+                HypId::empty(),
+                RustOutput::Workspace(WorkspaceCompilation::Message(CompilationMessage::new_info(
+                    "Compiling other_project v0.1.0"
+                )))
+            )),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Passed
+            ))
+        ]);
+
+        run_and_snapshot(session, test_name!());
+    }
+
+    #[test]
+    pub fn show_hyps_that_only_passed_after_a_retry_as_flaky()
+    {
+        let mut session = HypSession::new();
+
+        let flaky_hyp = || {
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Flaky
+            ))
+        };
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            flaky_hyp(),
+            HypSessionEvent::RunCompleted,
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            flaky_hyp(),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "multiply_2_and_2_is_4")),
+                HypState::Passed
+            )),
+            HypSessionEvent::RunCompleted
+        ]);
+
+        run_and_snapshot(session, test_name!());
+    }
+
+    #[test]
+    pub fn show_progress_of_a_running_run()
+    {
+        let mut session: HypSession<RustBridge> = HypSession::new();
+
+        let mut progress = RunProgress::new(3);
+        progress.record(HypState::Passed);
+        progress.record(HypState::Failed);
+        progress.elapsed = Duration::from_secs(4);
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Progress(RunProgress::new(3)),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Passed
+            )),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_3_is_5")),
+                HypState::Failed
+            )),
+            HypSessionEvent::Progress(progress)
+        ]);
+
+        let mut test_run_view = SessionView::new();
+
+        let ui = move |ui: &mut egui::Ui| {
+            _ = test_run_view.ui(ui, &session, None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+
+        harness.run();
+        harness.get_by_label("1 passed, 1 failed, 0 skipped, 1 remaining");
+        harness.get_by_label_contains("about 2.00 s left");
+        harness.fit_contents();
+        harness.snapshot(&test_name!());
+    }
+
+    #[test]
+    pub fn show_summary_once_the_run_completed()
+    {
+        let mut session: HypSession<RustBridge> = HypSession::new();
+
+        let mut progress = RunProgress::new(2);
+        progress.record(HypState::Passed);
+        progress.record(HypState::Failed);
+        progress.record(HypState::Skipped);
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                HypState::Passed
+            )),
+            HypSessionEvent::Hyp(HypReport::new_fixed(
+                RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_3_is_5")),
+                HypState::Failed
+            )),
+            HypSessionEvent::Summary(RunSummary {
+                progress,
+                elapsed: Duration::from_millis(4300)
+            }),
+            HypSessionEvent::RunCompleted
+        ]);
+
+        let mut test_run_view = SessionView::new();
+
+        let ui = move |ui: &mut egui::Ui| {
+            _ = test_run_view.ui(ui, &session, None);
+        };
+
+        let mut harness = Harness::new_ui(ui);
+
+        harness.run();
+        harness.get_by_label("1 passed, 1 failed, 1 skipped in 4.30 s");
+        harness.fit_contents();
+        harness.snapshot(&test_name!());
+    }
+
+    fn session_with_durations() -> HypSession<RustBridge>
+    {
+        let mut session = HypSession::new();
+
+        session.update_all([
+            HypSessionEvent::RunStarted(RunExtent::AllHyps),
+            HypSessionEvent::Hyp(
+                HypReport::new_fixed(
+                    RustHyp::new_single(HypId::new("simple_project", "simple_project", "add_2_and_2_is_4")),
+                    HypState::Passed
+                )
+                .with_duration(Duration::from_millis(12))
+            ),
+            HypSessionEvent::Hyp(
+                HypReport::new_fixed(
+                    RustHyp::new_single(HypId::new("simple_project", "simple_project", "multiply_2_and_2_is_4")),
+                    HypState::Passed
+                )
+                .with_duration(Duration::from_millis(1500))
+            ),
+            HypSessionEvent::RunCompleted
+        ]);
+
+        session
     }
 
     fn run_and_snapshot(session: HypSession<RustBridge>, snapshot_name: impl Into<String>)
     {
-        let mut test_run_view = SessionView;
+        let mut test_run_view = SessionView::new();
 
         let ui = move |ui: &mut egui::Ui| {
-            _ = test_run_view.ui(ui, &session);
+            _ = test_run_view.ui(ui, &session, None);
         };
 
         let mut harness = Harness::new_ui(ui);
@@ -1,15 +1,17 @@
+use std::time::Duration;
+
 use egui::Ui;
 
 use crate::session_view::HypUiAction;
 
 pub trait SpecializeSessionUi
 {
-    fn ui(&self, ui: &mut Ui) -> Option<HypUiAction>;
+    fn ui(&self, ui: &mut Ui, slow_threshold: Option<Duration>) -> Option<HypUiAction>;
 }
 
 impl<T> SpecializeSessionUi for T
 {
-    default fn ui(&self, _: &mut Ui) -> Option<HypUiAction>
+    default fn ui(&self, _: &mut Ui, _: Option<Duration>) -> Option<HypUiAction>
     {
         panic!(
             "missing UI specialization for session view and type {:?}",
@@ -17,3 +19,19 @@ impl<T> SpecializeSessionUi for T
         );
     }
 }
+
+pub trait SpecializeOutputUi
+{
+    fn output_ui(&self, ui: &mut Ui);
+}
+
+impl<T> SpecializeOutputUi for T
+{
+    default fn output_ui(&self, _: &mut Ui)
+    {
+        panic!(
+            "missing UI specialization for session output and type {:?}",
+            std::any::type_name::<T>()
+        );
+    }
+}
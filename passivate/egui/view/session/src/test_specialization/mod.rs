@@ -1,17 +1,34 @@
+use std::time::Duration;
+
 use egui::Ui;
 use passivate_model_core::hyp::Hyp;
-use passivate_testing::model::TestSession;
+use passivate_model_core::hyp_session::HypSession;
+use passivate_testing::model::{TestOutput, TestSession};
 
 use crate::session_view::{self, HypUiAction};
-use crate::specialize_session_ui::SpecializeSessionUi;
+use crate::specialize_session_ui::{SpecializeOutputUi, SpecializeSessionUi};
 
 impl SpecializeSessionUi for Hyp<TestSession>
 {
-    fn ui(&self, ui: &mut Ui) -> Option<HypUiAction>
+    fn ui(&self, ui: &mut Ui, slow_threshold: Option<Duration>) -> Option<HypUiAction>
     {
         let info = self.info();
         let name = info.to_string();
 
-        session_view::show_hyp(ui, self, name)
+        session_view::show_hyp(ui, self, name, slow_threshold)
+    }
+}
+
+impl SpecializeOutputUi for HypSession<TestSession>
+{
+    fn output_ui(&self, ui: &mut Ui)
+    {
+        for output in self.iter_output()
+        {
+            match output
+            {
+                TestOutput::Compilation(message) => session_view::compilation_label(ui, message)
+            }
+        }
     }
 }
@@ -31,6 +31,7 @@ pub fn default(views: &PassivateViews) -> DockingLayout
             views.details_dock().id(),
             views.coverage_dock().id(),
             views.configuration_dock().id(),
+            views.diagnostics_dock().id(),
         ]
     );
 
@@ -8,6 +8,7 @@ use passivate_egui_docking::docking_layout::DockingLayout;
 use passivate_egui_view_details::hyp_details::HypDetails;
 use passivate_id_chain_tree::id_chain::IdChain;
 use passivate_model_bridge::bridge::Bridge;
+use passivate_model_bridge::bridge_output::BridgeOutput;
 use passivate_model_core::hyp_session::HypSession;
 
 use crate::passivate_views::PassivateView;
@@ -97,7 +98,7 @@ fn internal_ui<'ctx, 'env, TBridge: Bridge>(
         PassivateView::Coverage(coverage_view) =>
         {
             coverage_view
-                .ui(ui, &state.coverage)
+                .ui(ui, &state.coverage, state.coverage_diff.as_ref())
                 .into_iter()
                 .map(PassivateStateChange::ConfigurationChanged)
                 .collect()
@@ -115,14 +116,29 @@ fn internal_ui<'ctx, 'env, TBridge: Bridge>(
                         snapshot_handles: view_state.snapshot_handles()
                     };
 
-                    details_view.ui(ui, Some(&hyp_details));
+                    if details_view.ui(ui, Some(&hyp_details), configuration.slow_hyp_threshold())
+                    {
+                        vec![PassivateStateChange::RunHyp(hyp)]
+                    }
+                    else
+                    {
+                        vec![]
+                    }
+                }
+                else
+                {
+                    vec![]
                 }
             }
             else
             {
-                details_view.ui::<TBridge>(ui, None);
+                details_view.ui::<TBridge>(ui, None, configuration.slow_hyp_threshold());
+                vec![]
             }
-
+        }
+        PassivateView::Diagnostics(diagnostics_view) =>
+        {
+            diagnostics_view.ui(ui, session.iter_output().filter_map(BridgeOutput::diagnostic));
             vec![]
         }
         PassivateView::Log(log_view) =>
@@ -133,7 +149,7 @@ fn internal_ui<'ctx, 'env, TBridge: Bridge>(
         PassivateView::HypRun(test_run_view) =>
         {
             test_run_view
-                .ui(ui, session)
+                .ui(ui, session, configuration.slow_hyp_threshold())
                 .map(PassivateStateChange::HypSelected)
                 .into_iter()
                 .collect()
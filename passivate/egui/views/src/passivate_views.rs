@@ -1,10 +1,12 @@
 use std::ops::{Deref, DerefMut};
 
+use camino::Utf8PathBuf;
 use passivate_egui_docking::docking_layout::DockId;
 use passivate_egui_docking::view::View;
 use passivate_egui_view_configuration::ConfigurationView;
 use passivate_egui_view_coverage::CoverageView;
 use passivate_egui_view_details::details_view::DetailsView;
+use passivate_egui_view_diagnostics::DiagnosticsView;
 use passivate_egui_view_log::LogView;
 use passivate_egui_view_session::session_view::SessionView;
 
@@ -13,6 +15,7 @@ pub enum PassivateView
     Configuration(ConfigurationDock),
     Coverage(CoverageDock),
     Details(DetailsDock),
+    Diagnostics(DiagnosticsDock),
     Log(LogDock),
     HypRun(SessionDock)
 }
@@ -23,7 +26,8 @@ pub struct PassivateViews
     details_view: PassivateView,
     coverage_view: PassivateView,
     configuration_view: PassivateView,
-    log_view: PassivateView
+    log_view: PassivateView,
+    diagnostics_view: PassivateView
 }
 
 impl PassivateViews
@@ -33,7 +37,8 @@ impl PassivateViews
         details_view: DetailsView,
         coverage_view: CoverageView,
         configuration_view: ConfigurationView,
-        log_view: LogView
+        log_view: LogView,
+        diagnostics_view: DiagnosticsView
     ) -> Self
     {
         Self {
@@ -41,23 +46,32 @@ impl PassivateViews
             details_view: PassivateView::Details(DetailsDock(details_view)),
             coverage_view: PassivateView::Coverage(CoverageDock(coverage_view)),
             configuration_view: PassivateView::Configuration(ConfigurationDock(configuration_view)),
-            log_view: PassivateView::Log(LogDock(log_view))
+            log_view: PassivateView::Log(LogDock(log_view)),
+            diagnostics_view: PassivateView::Diagnostics(DiagnosticsDock(diagnostics_view))
         }
     }
 
     pub fn stub() -> PassivateViews
     {
-        PassivateViews::new(SessionView, DetailsView, CoverageView, ConfigurationView::new(), LogView)
+        PassivateViews::new(
+            SessionView::new(),
+            DetailsView,
+            CoverageView::new(Utf8PathBuf::new()),
+            ConfigurationView::new(),
+            LogView,
+            DiagnosticsView
+        )
     }
 
-    pub fn get(&self) -> [&PassivateView; 5]
+    pub fn get(&self) -> [&PassivateView; 6]
     {
         [
             &self.session_view,
             &self.details_view,
             &self.coverage_view,
             &self.configuration_view,
-            &self.log_view
+            &self.log_view,
+            &self.diagnostics_view
         ]
     }
 
@@ -68,13 +82,14 @@ impl PassivateViews
             self.details_view,
             self.coverage_view,
             self.configuration_view,
-            self.log_view
+            self.log_view,
+            self.diagnostics_view
         ]
         .into_iter()
         .collect()
     }
 
-    pub fn ids(&self) -> [DockId; 5]
+    pub fn ids(&self) -> [DockId; 6]
     {
         self.get().map(|v| v.id())
     }
@@ -93,13 +108,14 @@ impl PassivateViews
         }
     }
 
-    pub fn except_hyp_run_view(&self) -> [&PassivateView; 4]
+    pub fn except_hyp_run_view(&self) -> [&PassivateView; 5]
     {
         [
             &self.details_view,
             &self.coverage_view,
             &self.configuration_view,
-            &self.log_view
+            &self.log_view,
+            &self.diagnostics_view
         ]
     }
 
@@ -117,13 +133,14 @@ impl PassivateViews
         }
     }
 
-    pub fn except_details_view(&self) -> [&PassivateView; 4]
+    pub fn except_details_view(&self) -> [&PassivateView; 5]
     {
         [
             &self.session_view,
             &self.coverage_view,
             &self.configuration_view,
-            &self.log_view
+            &self.log_view,
+            &self.diagnostics_view
         ]
     }
 
@@ -141,13 +158,14 @@ impl PassivateViews
         }
     }
 
-    pub fn except_coverage_view(&self) -> [&PassivateView; 4]
+    pub fn except_coverage_view(&self) -> [&PassivateView; 5]
     {
         [
             &self.session_view,
             &self.details_view,
             &self.configuration_view,
-            &self.log_view
+            &self.log_view,
+            &self.diagnostics_view
         ]
     }
 
@@ -165,9 +183,15 @@ impl PassivateViews
         }
     }
 
-    pub fn except_configuration_view(&self) -> [&PassivateView; 4]
+    pub fn except_configuration_view(&self) -> [&PassivateView; 5]
     {
-        [&self.session_view, &self.details_view, &self.coverage_view, &self.log_view]
+        [
+            &self.session_view,
+            &self.details_view,
+            &self.coverage_view,
+            &self.log_view,
+            &self.diagnostics_view
+        ]
     }
 
     pub fn log_view(&self) -> &LogView
@@ -184,13 +208,39 @@ impl PassivateViews
         }
     }
 
-    pub fn except_log_view(&self) -> [&PassivateView; 4]
+    pub fn except_log_view(&self) -> [&PassivateView; 5]
     {
         [
             &self.session_view,
             &self.details_view,
             &self.coverage_view,
-            &self.configuration_view
+            &self.configuration_view,
+            &self.diagnostics_view
+        ]
+    }
+
+    pub fn diagnostics_view(&self) -> &DiagnosticsView
+    {
+        self.diagnostics_dock()
+    }
+
+    pub fn diagnostics_dock(&self) -> &DiagnosticsDock
+    {
+        match &self.diagnostics_view
+        {
+            PassivateView::Diagnostics(diagnostics_view) => diagnostics_view,
+            _ => panic!("expected diagnostics view")
+        }
+    }
+
+    pub fn except_diagnostics_view(&self) -> [&PassivateView; 5]
+    {
+        [
+            &self.session_view,
+            &self.details_view,
+            &self.coverage_view,
+            &self.configuration_view,
+            &self.log_view
         ]
     }
 }
@@ -204,6 +254,7 @@ impl View for PassivateView
             PassivateView::Configuration(v) => v.id(),
             PassivateView::Coverage(v) => v.id(),
             PassivateView::Details(v) => v.id(),
+            PassivateView::Diagnostics(v) => v.id(),
             PassivateView::Log(v) => v.id(),
             PassivateView::HypRun(v) => v.id()
         }
@@ -216,6 +267,7 @@ impl View for PassivateView
             PassivateView::Configuration(v) => v.title(),
             PassivateView::Coverage(v) => v.title(),
             PassivateView::Details(v) => v.title(),
+            PassivateView::Diagnostics(v) => v.title(),
             PassivateView::Log(v) => v.title(),
             PassivateView::HypRun(v) => v.title()
         }
@@ -386,3 +438,36 @@ impl DerefMut for ConfigurationDock
         &mut self.0
     }
 }
+
+pub struct DiagnosticsDock(DiagnosticsView);
+
+impl View for DiagnosticsDock
+{
+    fn id(&self) -> DockId
+    {
+        DockId::from("diagnostics_view")
+    }
+
+    fn title(&self) -> String
+    {
+        String::from("Diagnostics")
+    }
+}
+
+impl Deref for DiagnosticsDock
+{
+    type Target = DiagnosticsView;
+
+    fn deref(&self) -> &Self::Target
+    {
+        &self.0
+    }
+}
+
+impl DerefMut for DiagnosticsDock
+{
+    fn deref_mut(&mut self) -> &mut Self::Target
+    {
+        &mut self.0
+    }
+}
@@ -28,6 +28,12 @@ impl HypId
         Self { parts }
     }
 
+    /// The id of no hyp in particular, for what concerns the run as a whole
+    pub fn empty() -> Self
+    {
+        Self { parts: Vec::new() }
+    }
+
     pub fn package_id(&self) -> PackageId
     {
         PackageId::from(&self.parts[0])
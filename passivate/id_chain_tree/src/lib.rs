@@ -1,3 +1,4 @@
+#![feature(push_mut)]
 #![feature(trait_alias)]
 
 pub mod chain_macro;
@@ -19,9 +19,18 @@ impl<TLink: ChainLink, TValue: IdChain<Link = TLink>> Tree<TLink, TValue>
         Self { values: Vec::new() }
     }
 
-    pub fn insert(&mut self, element: TValue)
+    /// Insert a value, replacing the value with the same chain if there already is one
+    pub fn insert(&mut self, element: TValue) -> &TValue
     {
-        self.values.push(Node::new(element));
+        match self.values.iter().position(|node| node.chain() == element.chain())
+        {
+            Some(index) =>
+            {
+                self.values[index].value = element;
+                &self.values[index].value
+            }
+            None => &self.values.push_mut(Node::new(element)).value
+        }
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &TValue>
@@ -29,6 +38,11 @@ impl<TLink: ChainLink, TValue: IdChain<Link = TLink>> Tree<TLink, TValue>
         self.values.iter().map(|node| &node.value)
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut TValue>
+    {
+        self.values.iter_mut().map(|node| &mut node.value)
+    }
+
     pub fn iter_nodes<'a>(&'a self) -> impl Iterator<Item = NodeView<'a, TLink, TValue>>
     {
         self.values.iter().map(|node| NodeView::new(node, self))
@@ -54,6 +68,11 @@ impl<TLink: ChainLink, TValue: IdChain<Link = TLink>> Tree<TLink, TValue>
         self.values.clear();
     }
 
+    pub fn retain(&mut self, mut keep: impl FnMut(&TValue) -> bool)
+    {
+        self.values.retain(|node| keep(&node.value));
+    }
+
     fn find_node(&self, chain: &[TLink]) -> Option<&Node<TValue>>
     {
         self.values.iter().find(|e| e.chain() == chain)
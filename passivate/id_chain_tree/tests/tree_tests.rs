@@ -102,3 +102,23 @@ pub fn iterate_children_of_a_node()
 
     assert_equal(node.iter_children(), [&child1, &child2]);
 }
+
+#[test]
+pub fn inserting_a_value_with_an_existing_chain_replaces_it()
+{
+    let mut tree = tree!(TestValue::new("ORIGINAL", "A"), TestValue::new("OTHER", "B"));
+
+    tree.insert(TestValue::new("REPLACEMENT", "A"));
+
+    assert_equal(tree.iter().map(|value| value.name.as_str()), ["REPLACEMENT", "OTHER"]);
+}
+
+#[test]
+pub fn retain_removes_values_that_are_not_kept()
+{
+    let mut tree = tree!(TestValue::new("KEEP", "A"), TestValue::new("REMOVE", "B"));
+
+    tree.retain(|value| value.name == "KEEP");
+
+    assert_equal(tree.iter().map(|value| value.name.as_str()), ["KEEP"]);
+}
@@ -4,12 +4,13 @@ use passivate_id_chain_tree::id_chain::IdChain;
 
 use crate::BridgeType;
 use crate::bridge_hyp::BridgeHyp;
+use crate::bridge_output::BridgeOutput;
 
 pub trait Bridge: PartialEq + Eq + Debug + Send + Sync + 'static
 {
     type IdLink: BridgeType;
     type Id: IdChain<Link = Self::IdLink> + Display + BridgeType;
-    type Output: Display + BridgeType;
+    type Output: BridgeOutput + Display + BridgeType;
     type HypInfo: BridgeHyp<Id = Self::Id> + IdChain<Link = Self::IdLink> + BridgeType;
-    type RunError: Debug + Send + Sync + 'static;
+    type RunError: Debug + Display + Send + Sync + 'static;
 }
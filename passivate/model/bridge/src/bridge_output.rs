@@ -0,0 +1,11 @@
+use crate::compiler_diagnostic::CompilerDiagnostic;
+use crate::hyp_failure::HypFailure;
+
+pub trait BridgeOutput
+{
+    /// The compiler diagnostic this output carries, if any
+    fn diagnostic(&self) -> Option<&CompilerDiagnostic>;
+
+    /// Why the hyp failed, if this output describes it
+    fn failure(&self) -> Option<&HypFailure>;
+}
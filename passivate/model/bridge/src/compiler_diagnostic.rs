@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticLevel
+{
+    Error,
+    Warning,
+    Note,
+    Help
+}
+
+impl Display for DiagnosticLevel
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            DiagnosticLevel::Error => write!(f, "error"),
+            DiagnosticLevel::Warning => write!(f, "warning"),
+            DiagnosticLevel::Note => write!(f, "note"),
+            DiagnosticLevel::Help => write!(f, "help")
+        }
+    }
+}
+
+/// A region of source code a diagnostic points at, lines and columns start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticSpan
+{
+    pub file: Utf8PathBuf,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub is_primary: bool,
+    pub label: Option<String>,
+    pub suggested_replacement: Option<String>
+}
+
+/// A message from the compiler, as reported by rustc's JSON output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerDiagnostic
+{
+    pub message: String,
+    pub code: Option<String>,
+    pub level: DiagnosticLevel,
+    pub spans: Vec<DiagnosticSpan>,
+    pub children: Vec<CompilerDiagnostic>,
+    /// The diagnostic as the compiler would print it to a terminal
+    pub rendered: Option<String>
+}
+
+impl CompilerDiagnostic
+{
+    pub fn primary_span(&self) -> Option<&DiagnosticSpan>
+    {
+        self.spans.iter().find(|span| span.is_primary)
+    }
+
+    pub fn secondary_spans(&self) -> impl Iterator<Item = &DiagnosticSpan>
+    {
+        self.spans.iter().filter(|span| !span.is_primary)
+    }
+
+    /// The file the diagnostic is about, none when it concerns no code in particular
+    pub fn file(&self) -> Option<&Utf8Path>
+    {
+        self.primary_span().map(|span| span.file.as_path())
+    }
+
+    /// Spans with a suggested replacement, whether they belong to this diagnostic or to one of its children
+    pub fn suggested_replacements(&self) -> Vec<&DiagnosticSpan>
+    {
+        self.spans
+            .iter()
+            .filter(|span| span.suggested_replacement.is_some())
+            .chain(self.children.iter().flat_map(|child| child.suggested_replacements()))
+            .collect()
+    }
+}
+
+impl Display for CompilerDiagnostic
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match &self.code
+        {
+            Some(code) => write!(f, "{}[{}]: {}", self.level, code, self.message),
+            None => write!(f, "{}: {}", self.level, self.message)
+        }
+    }
+}
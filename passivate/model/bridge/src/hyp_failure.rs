@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use camino::Utf8PathBuf;
+
+/// Where in the source a hyp failed, lines and columns start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureLocation
+{
+    pub file: Utf8PathBuf,
+    pub line: usize,
+    pub column: usize
+}
+
+impl Display for FailureLocation
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// The two values an assertion compared, as they were printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssertionValues
+{
+    pub left: String,
+    pub right: String
+}
+
+/// Why a hyp failed, recognised from its output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HypFailure
+{
+    pub message: String,
+    pub location: Option<FailureLocation>,
+    pub assertion: Option<AssertionValues>,
+    /// Lines of the backtrace, empty when none was captured
+    pub backtrace: Vec<String>
+}
+
+impl Display for HypFailure
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match &self.location
+        {
+            Some(location) => write!(f, "{} at {}", self.message, location),
+            None => write!(f, "{}", self.message)
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::bridge::Bridge;
 use crate::hyp_state::HypState;
 
@@ -12,7 +14,9 @@ pub enum HypReportState
 pub struct HypReport<TBridge: Bridge>
 {
     pub hyp_info: TBridge::HypInfo,
-    pub state: HypReportState
+    pub state: HypReportState,
+    /// How long the hyp took to run, if it ran
+    pub duration: Option<Duration>
 }
 
 impl<TBridge: Bridge> HypReport<TBridge>
@@ -21,7 +25,8 @@ impl<TBridge: Bridge> HypReport<TBridge>
     {
         Self {
             hyp_info,
-            state: HypReportState::Fixed(state)
+            state: HypReportState::Fixed(state),
+            duration: None
         }
     }
 
@@ -29,7 +34,14 @@ impl<TBridge: Bridge> HypReport<TBridge>
     {
         Self {
             hyp_info,
-            state: HypReportState::Derived
+            state: HypReportState::Derived,
+            duration: None
         }
     }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self
+    {
+        self.duration = Some(duration);
+        self
+    }
 }
@@ -1,3 +1,4 @@
+use camino::Utf8PathBuf;
 use passivate_configuration::configuration::PassivateConfiguration;
 use passivate_configuration::default_paths::DefaultPaths;
 use passivate_delegation::tx_rx::Tx;
@@ -11,6 +12,9 @@ pub trait RunHypsBridge<TBridge: Bridge>
 {
     fn run_all(&self, configuration: PassivateConfiguration, paths: DefaultPaths);
     fn run_single(&self, hyp: TBridge::Id, configuration: PassivateConfiguration, paths: DefaultPaths);
+    fn run_affected(&self, changed_files: Vec<Utf8PathBuf>, configuration: PassivateConfiguration, paths: DefaultPaths);
+    fn run_impacted(&self, changed_files: Vec<Utf8PathBuf>, configuration: PassivateConfiguration, paths: DefaultPaths);
+    fn run_failed_first(&self, failed_hyps: Vec<TBridge::Id>, configuration: PassivateConfiguration, paths: DefaultPaths);
 }
 
 impl<TTx, TBridge> RunHypsBridge<TBridge> for TTx
@@ -27,4 +31,19 @@ where
     {
         self.send(HypRunRequest::single(hyp_id, configuration, paths));
     }
+
+    fn run_affected(&self, changed_files: Vec<Utf8PathBuf>, configuration: PassivateConfiguration, paths: DefaultPaths)
+    {
+        self.send(HypRunRequest::affected(changed_files, configuration, paths));
+    }
+
+    fn run_impacted(&self, changed_files: Vec<Utf8PathBuf>, configuration: PassivateConfiguration, paths: DefaultPaths)
+    {
+        self.send(HypRunRequest::impacted(changed_files, configuration, paths));
+    }
+
+    fn run_failed_first(&self, failed_hyps: Vec<TBridge::Id>, configuration: PassivateConfiguration, paths: DefaultPaths)
+    {
+        self.send(HypRunRequest::failed_first(failed_hyps, configuration, paths));
+    }
 }
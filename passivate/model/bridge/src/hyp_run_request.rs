@@ -32,6 +32,33 @@ impl<TBridge: Bridge> HypRunRequest<TBridge>
         }
     }
 
+    pub fn affected(changed_files: Vec<Utf8PathBuf>, configuration: PassivateConfiguration, paths: DefaultPaths) -> Self
+    {
+        Self {
+            kind: HypRunRequestKind::Affected { changed_files },
+            configuration,
+            paths
+        }
+    }
+
+    pub fn impacted(changed_files: Vec<Utf8PathBuf>, configuration: PassivateConfiguration, paths: DefaultPaths) -> Self
+    {
+        Self {
+            kind: HypRunRequestKind::Impacted { changed_files },
+            configuration,
+            paths
+        }
+    }
+
+    pub fn failed_first(failed_hyps: Vec<TBridge::Id>, configuration: PassivateConfiguration, paths: DefaultPaths) -> Self
+    {
+        Self {
+            kind: HypRunRequestKind::FailedFirst { failed_hyps },
+            configuration,
+            paths
+        }
+    }
+
     pub fn passivate_directory(&self) -> Utf8PathBuf
     {
         self.configuration
@@ -49,6 +76,21 @@ pub enum HypRunRequestKind<TBridge: Bridge>
     Single
     {
         hyp_id: TBridge::Id
+    },
+    /// Hyps of the packages owning the changed files, and of the packages depending on them
+    Affected
+    {
+        changed_files: Vec<Utf8PathBuf>
+    },
+    /// Hyps whose last recorded coverage executed the changed lines of the changed files
+    Impacted
+    {
+        changed_files: Vec<Utf8PathBuf>
+    },
+    /// Every hyp, starting with those that failed last time
+    FailedFirst
+    {
+        failed_hyps: Vec<TBridge::Id>
     }
 }
 
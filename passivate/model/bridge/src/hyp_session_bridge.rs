@@ -2,14 +2,17 @@ use mockall::mock;
 use passivate_delegation::tx_rx::Tx;
 
 use crate::bridge::Bridge;
+use crate::compiler_diagnostic::CompilerDiagnostic;
 use crate::hyp_report::HypReport;
 use crate::hyp_session_event::HypSessionEvent;
 use crate::output_report::OutputReport;
+use crate::run_extent::RunExtent;
+use crate::run_progress::{RunProgress, RunSummary};
 
 /// Interfaces from a test runner implementation to communicate changes to the session state.
 pub trait StartRunBridge<TBridge: Bridge>: Send + Sync + 'static
 {
-    fn start_run(&mut self);
+    fn start_run(&mut self, extent: RunExtent);
 }
 
 pub trait SendOutputBridge<TBridge: Bridge>: Send + Sync + 'static
@@ -37,15 +40,30 @@ pub trait RunErrorBridge<TBridge: Bridge>: Send + Sync + 'static
     fn run_error(&mut self, run_error: TBridge::RunError);
 }
 
+pub trait BuildFailedBridge<TBridge: Bridge>: Send + Sync + 'static
+{
+    fn build_failed(&mut self, errors: Vec<CompilerDiagnostic>);
+}
+
+pub trait SendProgressBridge<TBridge: Bridge>: Send + Sync + 'static
+{
+    fn send_progress(&mut self, progress: RunProgress);
+}
+
+pub trait SendSummaryBridge<TBridge: Bridge>: Send + Sync + 'static
+{
+    fn send_summary(&mut self, summary: RunSummary);
+}
+
 impl<TTx, TBridge> StartRunBridge<TBridge> for TTx
 where
     TBridge: Bridge,
     TTx: Tx<HypSessionEvent<TBridge>> + Send + Sync + 'static
 {
-    fn start_run(&mut self)
+    fn start_run(&mut self, extent: RunExtent)
     {
         log::info!("start_run");
-        self.send(HypSessionEvent::RunStarted);
+        self.send(HypSessionEvent::RunStarted(extent));
     }
 }
 
@@ -109,12 +127,48 @@ where
     }
 }
 
+impl<TTx, TBridge: Bridge> BuildFailedBridge<TBridge> for TTx
+where
+    TBridge: Bridge,
+    TTx: Tx<HypSessionEvent<TBridge>> + Send + Sync + 'static
+{
+    fn build_failed(&mut self, errors: Vec<CompilerDiagnostic>)
+    {
+        log::info!("build_failed: {} errors", errors.len());
+        self.send(HypSessionEvent::BuildFailed(errors));
+    }
+}
+
+impl<TTx, TBridge: Bridge> SendProgressBridge<TBridge> for TTx
+where
+    TBridge: Bridge,
+    TTx: Tx<HypSessionEvent<TBridge>> + Send + Sync + 'static
+{
+    fn send_progress(&mut self, progress: RunProgress)
+    {
+        log::info!("send_progress");
+        self.send(HypSessionEvent::Progress(progress));
+    }
+}
+
+impl<TTx, TBridge: Bridge> SendSummaryBridge<TBridge> for TTx
+where
+    TBridge: Bridge,
+    TTx: Tx<HypSessionEvent<TBridge>> + Send + Sync + 'static
+{
+    fn send_summary(&mut self, summary: RunSummary)
+    {
+        log::info!("send_summary");
+        self.send(HypSessionEvent::Summary(summary));
+    }
+}
+
 mock! {
     pub HypSessionBridge<TBridge: Bridge> { }
 
     impl<TBridge: Bridge> StartRunBridge<TBridge> for HypSessionBridge<TBridge>
     {
-        fn start_run(&mut self);
+        fn start_run(&mut self, extent: RunExtent);
     }
 
     impl<TBridge: Bridge> SendOutputBridge<TBridge> for HypSessionBridge<TBridge>
@@ -141,6 +195,21 @@ mock! {
     {
         fn run_error(&mut self, run_error: TBridge::RunError);
     }
+
+    impl<TBridge: Bridge> BuildFailedBridge<TBridge> for HypSessionBridge<TBridge>
+    {
+        fn build_failed(&mut self, errors: Vec<CompilerDiagnostic>);
+    }
+
+    impl<TBridge: Bridge> SendProgressBridge<TBridge> for HypSessionBridge<TBridge>
+    {
+        fn send_progress(&mut self, progress: RunProgress);
+    }
+
+    impl<TBridge: Bridge> SendSummaryBridge<TBridge> for HypSessionBridge<TBridge>
+    {
+        fn send_summary(&mut self, summary: RunSummary);
+    }
 }
 
 pub fn stub<TBridge: Bridge>() -> MockHypSessionBridge<TBridge>
@@ -153,6 +222,9 @@ pub fn stub<TBridge: Bridge>() -> MockHypSessionBridge<TBridge>
     mock.expect_cancel_run().return_const(());
     mock.expect_complete_run().return_const(());
     mock.expect_run_error().return_const(());
+    mock.expect_build_failed().return_const(());
+    mock.expect_send_progress().return_const(());
+    mock.expect_send_summary().return_const(());
 
     mock
 }
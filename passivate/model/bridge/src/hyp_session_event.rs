@@ -1,8 +1,11 @@
 use std::fmt::Display;
 
 use crate::bridge::Bridge;
+use crate::compiler_diagnostic::CompilerDiagnostic;
 use crate::hyp_report::HypReport;
 use crate::output_report::OutputReport;
+use crate::run_extent::RunExtent;
+use crate::run_progress::{RunProgress, RunSummary};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CompilationMessageKind
@@ -78,6 +81,14 @@ impl ConsoleOutput
             kind: ConsoleOutputKind::StdErr
         }
     }
+
+    pub fn new_combined(content: impl Into<String>) -> Self
+    {
+        Self {
+            content: content.into(),
+            kind: ConsoleOutputKind::Combined
+        }
+    }
 }
 
 impl Display for ConsoleOutput
@@ -92,18 +103,26 @@ impl Display for ConsoleOutput
 pub enum ConsoleOutputKind
 {
     StdOut,
-    StdErr
+    StdErr,
+    /// Stdout and stderr interleaved, for hyps whose output was captured as one stream
+    Combined
 }
 
 #[derive(Debug)]
 pub enum HypSessionEvent<TBridge: Bridge>
 {
-    RunStarted,
+    /// Sent before the hyps to run are built, so before it is known how many there are, which the first progress tells
+    RunStarted(RunExtent),
     Output(OutputReport<TBridge>),
     Hyp(HypReport<TBridge>),
     RunCompleted,
     RunCancelled,
-    RunError(TBridge::RunError)
+    RunError(TBridge::RunError),
+    BuildFailed(Vec<CompilerDiagnostic>),
+    /// Sent once the hyps to run are listed, and again each time one of them finishes
+    Progress(RunProgress),
+    /// Sent once every listed hyp finished
+    Summary(RunSummary)
 }
 
 impl<TBridge: Bridge> Display for HypSessionEvent<TBridge>
@@ -112,12 +131,15 @@ impl<TBridge: Bridge> Display for HypSessionEvent<TBridge>
     {
         match self
         {
-            HypSessionEvent::RunStarted => write!(f, "Run Started"),
+            HypSessionEvent::RunStarted(_) => write!(f, "Run Started"),
             HypSessionEvent::Output(output_report) => write!(f, "Output: {}", output_report.id()),
             HypSessionEvent::Hyp(hyp_report) => write!(f, "Hyp: {}", hyp_report.hyp_info),
             HypSessionEvent::RunCompleted => write!(f, "Run Completed"),
             HypSessionEvent::RunCancelled => write!(f, "Run Cancelled"),
-            HypSessionEvent::RunError(_) => write!(f, "Run Error")
+            HypSessionEvent::RunError(_) => write!(f, "Run Error"),
+            HypSessionEvent::BuildFailed(_) => write!(f, "Build Failed"),
+            HypSessionEvent::Progress(progress) => write!(f, "Progress: {} of {}", progress.finished(), progress.total),
+            HypSessionEvent::Summary(_) => write!(f, "Summary")
         }
     }
 }
@@ -128,8 +150,12 @@ impl<TBridge: Bridge> PartialEq for HypSessionEvent<TBridge>
     {
         match (self, other)
         {
+            (Self::RunStarted(l0), Self::RunStarted(r0)) => l0 == r0,
             (Self::Output(l0), Self::Output(r0)) => l0 == r0,
             (Self::Hyp(l0), Self::Hyp(r0)) => l0 == r0,
+            (Self::BuildFailed(l0), Self::BuildFailed(r0)) => l0 == r0,
+            (Self::Progress(l0), Self::Progress(r0)) => l0 == r0,
+            (Self::Summary(l0), Self::Summary(r0)) => l0 == r0,
             (Self::RunError(_), Self::RunError(_)) => panic!("attempt to compare HypSessionEvent::RunError for equality"),
             _ => core::mem::discriminant(self) == core::mem::discriminant(other)
         }
@@ -4,5 +4,13 @@ pub enum HypState
     Passed,
     Failed,
     Unknown,
-    Running
+    Running,
+    /// The hyp failed, then passed when it was retried
+    Flaky,
+    /// The hyp is ignored, so it did not run
+    Skipped,
+    /// The hyp ran for too long, so it was terminated
+    TimedOut,
+    /// The hyps could not be built, so none of them ran
+    BuildFailed
 }
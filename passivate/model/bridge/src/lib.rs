@@ -2,6 +2,9 @@
 
 pub mod bridge;
 pub mod bridge_hyp;
+pub mod bridge_output;
+pub mod compiler_diagnostic;
+pub mod hyp_failure;
 pub mod hyp_report;
 pub mod hyp_run_bridge;
 pub mod hyp_run_request;
@@ -9,6 +12,8 @@ pub mod hyp_session_bridge;
 pub mod hyp_session_event;
 pub mod hyp_state;
 pub mod output_report;
+pub mod run_extent;
+pub mod run_progress;
 pub mod source_change_bridge;
 pub mod source_change_event;
 
@@ -0,0 +1,8 @@
+/// Whether a run runs every hyp, which tells whether a hyp it did not report still exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunExtent
+{
+    AllHyps,
+    /// Only some hyps run, such as a single hyp or those impacted by a change, so the others are kept as they were
+    SomeHyps
+}
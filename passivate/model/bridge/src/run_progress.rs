@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use crate::hyp_state::HypState;
+
+/// How many of the hyps listed for a run have finished, and how
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunProgress
+{
+    /// Hyps listed to run, which excludes skipped hyps
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// How long the listed hyps have been running for
+    pub elapsed: Duration
+}
+
+impl RunProgress
+{
+    pub fn new(total: usize) -> Self
+    {
+        Self {
+            total,
+            ..Self::default()
+        }
+    }
+
+    /// Count a hyp that reached the given state, states of hyps that have not finished are not counted
+    pub fn record(&mut self, state: HypState)
+    {
+        match state
+        {
+            HypState::Passed | HypState::Flaky => self.passed += 1,
+            HypState::Failed | HypState::TimedOut => self.failed += 1,
+            HypState::Skipped => self.skipped += 1,
+            HypState::Unknown | HypState::Running | HypState::BuildFailed =>
+            {}
+        }
+    }
+
+    pub fn finished(&self) -> usize
+    {
+        self.passed + self.failed
+    }
+
+    pub fn remaining(&self) -> usize
+    {
+        self.total.saturating_sub(self.finished())
+    }
+
+    /// Part of the listed hyps that finished, from 0 to 1
+    pub fn fraction(&self) -> f32
+    {
+        if self.total == 0
+        {
+            1.0
+        }
+        else
+        {
+            self.finished() as f32 / self.total as f32
+        }
+    }
+
+    /// How much longer the remaining hyps are expected to run for, at the pace of those that finished
+    pub fn eta(&self) -> Option<Duration>
+    {
+        if self.finished() == 0 || self.remaining() == 0
+        {
+            return None;
+        }
+
+        Some(self.elapsed.mul_f64(self.remaining() as f64 / self.finished() as f64))
+    }
+}
+
+/// The final counts of a run that ran all its hyps as nextest reported them, and how long that took
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunSummary
+{
+    pub progress: RunProgress,
+    pub elapsed: Duration
+}
@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use std::time::Duration;
 
 use passivate_id_chain_tree::id_chain::IdChain;
 use passivate_model_bridge::bridge::Bridge;
@@ -10,7 +11,12 @@ pub struct Hyp<TBridge: Bridge>
 {
     info: TBridge::HypInfo,
     state: HypState,
-    output: Vec<TBridge::Output>
+    output: Vec<TBridge::Output>,
+    duration: Option<Duration>,
+    /// How many runs, including earlier ones, the hyp only passed after being retried in
+    flaky_runs: u32,
+    /// Whether the hyp was reported by an earlier run and not yet by the latest
+    stale: bool
 }
 
 impl<TBridge: Bridge> Hyp<TBridge>
@@ -20,10 +26,26 @@ impl<TBridge: Bridge> Hyp<TBridge>
         Self {
             info,
             state,
-            output: Vec::new()
+            output: Vec::new(),
+            duration: None,
+            flaky_runs: u32::from(state == HypState::Flaky),
+            stale: false
         }
     }
 
+    pub fn with_duration(mut self, duration: Option<Duration>) -> Self
+    {
+        self.duration = duration;
+        self
+    }
+
+    /// Carries over how flaky the hyp was in the runs before this one
+    pub fn with_history_of(mut self, earlier: &Hyp<TBridge>) -> Self
+    {
+        self.flaky_runs += earlier.flaky_runs;
+        self
+    }
+
     pub fn id(&self) -> &TBridge::Id
     {
         self.info.id()
@@ -53,6 +75,27 @@ impl<TBridge: Bridge> Hyp<TBridge>
     {
         self.state
     }
+
+    /// How long the hyp took to run, none when it has not finished
+    pub fn duration(&self) -> Option<Duration>
+    {
+        self.duration
+    }
+
+    pub fn flaky_runs(&self) -> u32
+    {
+        self.flaky_runs
+    }
+
+    pub fn is_stale(&self) -> bool
+    {
+        self.stale
+    }
+
+    pub fn mark_stale(&mut self)
+    {
+        self.stale = true;
+    }
 }
 
 impl<TBridge: Bridge> IdChain for Hyp<TBridge>
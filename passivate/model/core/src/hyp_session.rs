@@ -4,10 +4,13 @@ use passivate_delegation::tx_rx::Rx;
 use passivate_id_chain_tree::id_chain::IdChain;
 use passivate_id_chain_tree::tree::Tree;
 use passivate_model_bridge::bridge::Bridge;
+use passivate_model_bridge::compiler_diagnostic::CompilerDiagnostic;
 use passivate_model_bridge::hyp_report::{HypReport, HypReportState};
 use passivate_model_bridge::hyp_session_event::HypSessionEvent;
 use passivate_model_bridge::hyp_state::HypState;
 use passivate_model_bridge::output_report::OutputReport;
+use passivate_model_bridge::run_extent::RunExtent;
+use passivate_model_bridge::run_progress::{RunProgress, RunSummary};
 
 use crate::hyp::Hyp;
 use crate::hyp_session_change::HypSessionChange;
@@ -25,7 +28,12 @@ struct Session<TBridge: Bridge>
 {
     activity: HypState,
     hyps: Tree<TBridge::IdLink, Hyp<TBridge>>,
-    output: Vec<TBridge::Output>
+    output: Vec<TBridge::Output>,
+    /// Why the latest run failed, when it failed as a whole rather than through its hyps
+    run_error: Option<String>,
+    build_errors: Vec<CompilerDiagnostic>,
+    progress: Option<RunProgress>,
+    summary: Option<RunSummary>
 }
 
 type ChangeResult<'a, TBridge> = Result<Option<HypSessionChange<'a, TBridge>>, HypSessionEvent<TBridge>>;
@@ -54,11 +62,34 @@ impl<TBridge: Bridge> HypSession<TBridge>
         &self.session.hyps
     }
 
+    pub fn failure_reason(&self) -> Option<&str>
+    {
+        self.session.run_error.as_deref()
+    }
+
+    /// The errors that made the latest build fail
+    pub fn build_errors(&self) -> &[CompilerDiagnostic]
+    {
+        &self.session.build_errors
+    }
+
     pub fn iter_output(&self) -> impl Iterator<Item = &TBridge::Output>
     {
         self.session.output.iter()
     }
 
+    /// How far the latest run has come, none until its hyps are listed
+    pub fn progress(&self) -> Option<&RunProgress>
+    {
+        self.session.progress.as_ref()
+    }
+
+    /// The outcome of the latest run, none until all its hyps finished
+    pub fn summary(&self) -> Option<&RunSummary>
+    {
+        self.session.summary.as_ref()
+    }
+
     pub fn update_all(&mut self, events: impl IntoIterator<Item = HypSessionEvent<TBridge>>)
     {
         for event in events
@@ -113,11 +144,17 @@ impl<TBridge: Bridge> Session<TBridge>
 
         match self.activity
         {
-            HypState::Unknown | HypState::Passed | HypState::Failed =>
+            HypState::Unknown
+            | HypState::Passed
+            | HypState::Failed
+            | HypState::Flaky
+            | HypState::Skipped
+            | HypState::TimedOut
+            | HypState::BuildFailed =>
             {
                 match event
                 {
-                    HypSessionEvent::RunStarted => self.start_run(),
+                    HypSessionEvent::RunStarted(extent) => self.start_run(extent),
                     _ => Err(event)
                 }
             }
@@ -130,17 +167,34 @@ impl<TBridge: Bridge> Session<TBridge>
                     HypSessionEvent::RunCompleted => self.complete_run(),
                     HypSessionEvent::RunError(run_error) => self.run_error(run_error),
                     HypSessionEvent::RunCancelled => self.cancel_run(),
+                    HypSessionEvent::BuildFailed(errors) => self.build_failed(errors),
+                    HypSessionEvent::Progress(progress) => self.progress(progress),
+                    HypSessionEvent::Summary(summary) => self.summary(summary),
                     _ => Err(event)
                 }
             }
         }
     }
 
-    fn start_run(&mut self) -> ChangeResult<'_, TBridge>
+    fn start_run(&mut self, extent: RunExtent) -> ChangeResult<'_, TBridge>
     {
         self.activity = HypState::Running;
 
-        self.hyps.clear();
+        // Hyps of the previous run stay visible until the new run reports them again, a run of only some hyps leaves
+        // the others as they were, so only a run of every hyp drops those it does not report
+        if extent == RunExtent::AllHyps
+        {
+            for hyp in self.hyps.iter_mut()
+            {
+                hyp.mark_stale();
+            }
+        }
+
+        self.output.clear();
+        self.run_error = None;
+        self.build_errors.clear();
+        self.progress = None;
+        self.summary = None;
 
         Ok(None)
     }
@@ -156,6 +210,9 @@ impl<TBridge: Bridge> Session<TBridge>
     {
         self.activity = HypState::Passed;
 
+        // Hyps the completed run did not report no longer exist
+        self.hyps.retain(|hyp| !hyp.is_stale());
+
         Ok(None)
     }
 
@@ -185,14 +242,60 @@ impl<TBridge: Bridge> Session<TBridge>
     fn hyp(&mut self, hyp_report: HypReport<TBridge>) -> ChangeResult<'_, TBridge>
     {
         let (info, state) = (hyp_report.hyp_info, hyp_report.state);
+        let is_derived = matches!(state, HypReportState::Derived);
 
         let hyp: Hyp<TBridge> = match state
         {
             HypReportState::Fixed(hyp_state) => Hyp::new(info, hyp_state),
             HypReportState::Derived => Hyp::new(info, HypState::Unknown)
+        }
+        .with_duration(hyp_report.duration);
+
+        let earlier = self.hyps.get(hyp.chain());
+        let is_update = earlier.is_some();
+
+        // Listing a hyp that is already known keeps what an earlier run reported until it finishes again
+        if is_derived && is_update
+        {
+            return Ok(None);
+        }
+
+        let hyp = match earlier
+        {
+            Some(earlier) => hyp.with_history_of(earlier),
+            None => hyp
         };
 
-        self.hyps.insert(hyp);
+        let hyp = self.hyps.insert(hyp);
+
+        if is_update
+        {
+            Ok(Some(HypSessionChange::HypUpdated(hyp)))
+        }
+        else
+        {
+            Ok(Some(HypSessionChange::NewHyp(hyp)))
+        }
+    }
+
+    fn progress(&mut self, progress: RunProgress) -> ChangeResult<'_, TBridge>
+    {
+        self.progress = Some(progress);
+
+        Ok(None)
+    }
+
+    fn summary(&mut self, summary: RunSummary) -> ChangeResult<'_, TBridge>
+    {
+        self.summary = Some(summary);
+
+        Ok(None)
+    }
+
+    fn build_failed(&mut self, errors: Vec<CompilerDiagnostic>) -> ChangeResult<'_, TBridge>
+    {
+        self.activity = HypState::BuildFailed;
+        self.build_errors = errors;
 
         Ok(None)
     }
@@ -203,6 +306,7 @@ impl<TBridge: Bridge> Session<TBridge>
     ) -> Result<Option<HypSessionChange<'_, TBridge>>, HypSessionEvent<TBridge>>
     {
         self.activity = HypState::Failed;
+        self.run_error = Some(run_error.to_string());
 
         Ok(None)
     }
@@ -215,7 +319,11 @@ impl<TBridge: Bridge> Default for HypSession<TBridge>
         let session = Session {
             activity: HypState::Unknown,
             hyps: Tree::new(),
-            output: Vec::new()
+            output: Vec::new(),
+            run_error: None,
+            build_errors: Vec::new(),
+            progress: None,
+            summary: None
         };
 
         HypSession { session, error: None }
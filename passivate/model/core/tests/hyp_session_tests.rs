@@ -1,14 +1,20 @@
 #[macro_use]
 extern crate assert_matches;
 
+use std::time::Duration;
+
 use itertools::assert_equal;
 use passivate_id_chain_tree::chain;
 use passivate_id_chain_tree::id_chain::IdChain;
+use passivate_model_bridge::compiler_diagnostic::{CompilerDiagnostic, DiagnosticLevel};
 use passivate_model_bridge::hyp_report::HypReport;
 use passivate_model_bridge::hyp_session_bridge::*;
 use passivate_model_bridge::hyp_session_event::{CompilationMessage, CompilationMessageKind, HypSessionEvent};
 use passivate_model_bridge::hyp_state::HypState;
 use passivate_model_bridge::output_report::OutputReport;
+use passivate_model_bridge::run_extent::RunExtent;
+use passivate_model_bridge::run_progress::{RunProgress, RunSummary};
+use passivate_model_core::hyp_session_change::HypSessionChange;
 use passivate_model_core::hyp_session_state_error::HypSessionStateError;
 use passivate_testing::model::{TestHyp, TestHypKind, TestId, TestOutput, TestProject, TestSession};
 
@@ -67,13 +73,13 @@ pub fn starting_a_started_session_is_error_state()
 {
     let mut session = new_started_session();
 
-    session.start_run();
+    session.start_run(RunExtent::AllHyps);
 
     assert_matches!(session.activity(), Err(error) =>
     {
         assert_matches!(error, HypSessionStateError::UnexpectedEvent { event } =>
         {
-            assert_eq!(HypSessionEvent::RunStarted, *event);
+            assert_eq!(HypSessionEvent::RunStarted(RunExtent::AllHyps), *event);
         });
     });
 }
@@ -83,14 +89,14 @@ pub fn new_errors_do_not_replace_original_error_state()
 {
     let mut session = new_started_session();
 
-    session.start_run();
+    session.start_run(RunExtent::AllHyps);
     session.complete_run();
 
     assert_matches!(session.activity(), Err(error) =>
     {
         assert_matches!(error, HypSessionStateError::UnexpectedEvent { event } =>
         {
-            assert_eq!(HypSessionEvent::RunStarted, *event);
+            assert_eq!(HypSessionEvent::RunStarted(RunExtent::AllHyps), *event);
         });
     });
 }
@@ -133,6 +139,21 @@ pub fn last_session_output_is_most_recent()
     });
 }
 
+#[test]
+pub fn session_output_is_cleared_when_a_new_run_starts()
+{
+    let mut session = new_started_session();
+
+    session.send_output(OutputReport::new(
+        TestId::empty(),
+        TestOutput::Compilation(CompilationMessage::new_error("example error"))
+    ));
+    session.complete_run();
+    session.start_run(RunExtent::AllHyps);
+
+    assert_matches!(session.iter_output().next(), None);
+}
+
 #[test]
 pub fn output_for_unknown_hyp_is_error_state()
 {
@@ -183,6 +204,27 @@ pub fn run_error_leaves_session_completed_but_in_failed_state()
     assert_matches!(session.activity(), Ok(HypState::Failed));
 }
 
+#[test]
+pub fn run_error_is_kept_as_reason_for_failure()
+{
+    let mut session = new_started_session();
+
+    session.run_error(String::from("coverage is below the minimum"));
+
+    assert_eq!(Some("coverage is below the minimum"), session.failure_reason());
+}
+
+#[test]
+pub fn starting_a_run_clears_previous_run_error()
+{
+    let mut session = new_started_session();
+
+    session.run_error(String::from("coverage is below the minimum"));
+    session.start_run(RunExtent::AllHyps);
+
+    assert_eq!(None, session.failure_reason());
+}
+
 #[test]
 pub fn passed_hyp_has_passed_state()
 {
@@ -198,6 +240,20 @@ pub fn passed_hyp_has_passed_state()
     assert_matches!(session.hyps().get(hyp_id.chain()).unwrap().state(), HypState::Passed);
 }
 
+#[test]
+pub fn hyp_keeps_the_duration_it_ran_for()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(passed_hyp(hyp_id.clone()).with_duration(Duration::from_millis(250)));
+
+    assert_eq!(
+        Some(Duration::from_millis(250)),
+        session.hyps().get(hyp_id.chain()).unwrap().duration()
+    );
+}
+
 #[test]
 pub fn cancelled_started_session_is_in_unknown_state()
 {
@@ -208,11 +264,332 @@ pub fn cancelled_started_session_is_in_unknown_state()
     assert_matches!(session.state(), HypState::Unknown);
 }
 
+#[test]
+pub fn started_hyp_is_running()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(HypReport::new_fixed(
+        TestHypKind::Hyp(TestHyp::new(hyp_id.clone())),
+        HypState::Running
+    ));
+
+    assert_matches!(session.hyps().get(hyp_id.chain()).unwrap().state(), HypState::Running);
+}
+
+#[test]
+pub fn first_report_of_a_hyp_is_a_new_hyp()
+{
+    let mut session = new_started_session();
+
+    let change = session.update(HypSessionEvent::Hyp(passed_hyp(TestId::from("example"))));
+
+    assert_matches!(change, Some(HypSessionChange::NewHyp(hyp)) => assert_matches!(hyp.state(), HypState::Passed));
+}
+
+#[test]
+pub fn finishing_a_running_hyp_updates_it()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(HypReport::new_fixed(
+        TestHypKind::Hyp(TestHyp::new(hyp_id.clone())),
+        HypState::Running
+    ));
+
+    let change = session.update(HypSessionEvent::Hyp(passed_hyp(hyp_id)));
+
+    assert_matches!(change, Some(HypSessionChange::HypUpdated(hyp)) => assert_matches!(hyp.state(), HypState::Passed));
+    assert_eq!(1, session.hyps().iter().count());
+}
+
+#[test]
+pub fn hyps_of_previous_run_stay_stale_when_a_new_run_starts()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(passed_hyp(hyp_id.clone()));
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+
+    let hyp = session.hyps().get(hyp_id.chain()).unwrap();
+
+    assert_matches!(hyp.state(), HypState::Passed);
+    assert!(hyp.is_stale());
+}
+
+#[test]
+pub fn hyp_reported_again_is_no_longer_stale()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(passed_hyp(hyp_id.clone()));
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+    session.send_hyp(HypReport::new_fixed(
+        TestHypKind::Hyp(TestHyp::new(hyp_id.clone())),
+        HypState::Failed
+    ));
+
+    assert_equal(
+        session.hyps().iter().map(|hyp| (hyp.state(), hyp.is_stale())),
+        [(HypState::Failed, false)]
+    );
+}
+
+#[test]
+pub fn hyps_not_reported_by_a_completed_run_are_removed()
+{
+    let mut session = new_started_session();
+
+    session.send_hyp(passed_hyp(TestId::from("vanished")));
+    session.send_hyp(passed_hyp(TestId::from("kept")));
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+    session.send_hyp(passed_hyp(TestId::from("kept")));
+    session.complete_run();
+
+    assert_equal(session.hyps().iter().map(|hyp| hyp.info().to_string()), ["kept"]);
+}
+
+#[test]
+pub fn hyps_a_run_of_some_hyps_did_not_report_are_kept_as_they_were()
+{
+    let mut session = new_started_session();
+
+    session.send_hyp(flaky_hyp(TestId::from("other")));
+    session.send_hyp(passed_hyp(TestId::from("single")));
+    session.complete_run();
+
+    session.start_run(RunExtent::SomeHyps);
+    session.send_hyp(passed_hyp(TestId::from("single")));
+    session.complete_run();
+
+    assert_equal(
+        session
+            .hyps()
+            .iter()
+            .map(|hyp| (hyp.info().to_string(), hyp.state(), hyp.is_stale(), hyp.flaky_runs())),
+        [
+            (String::from("other"), HypState::Flaky, false, 1),
+            (String::from("single"), HypState::Passed, false, 0)
+        ]
+    );
+}
+
+#[test]
+pub fn listed_hyp_is_unknown_until_it_finishes()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(HypReport::new_derived(TestHypKind::Hyp(TestHyp::new(hyp_id.clone()))));
+
+    assert_matches!(session.hyps().get(hyp_id.chain()).unwrap().state(), HypState::Unknown);
+
+    session.send_hyp(passed_hyp(hyp_id.clone()));
+
+    assert_matches!(session.hyps().get(hyp_id.chain()).unwrap().state(), HypState::Passed);
+}
+
+#[test]
+pub fn listing_a_hyp_of_the_previous_run_keeps_its_result_until_it_finishes()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(passed_hyp(hyp_id.clone()));
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+    session.send_hyp(HypReport::new_derived(TestHypKind::Hyp(TestHyp::new(hyp_id.clone()))));
+
+    assert_equal(
+        session.hyps().iter().map(|hyp| (hyp.state(), hyp.is_stale())),
+        [(HypState::Passed, true)]
+    );
+}
+
+#[test]
+pub fn failed_build_leaves_session_in_build_failed_state()
+{
+    let mut session = new_started_session();
+
+    session.build_failed(vec![example_build_error()]);
+
+    assert_matches!(session.activity(), Ok(HypState::BuildFailed));
+}
+
+#[test]
+pub fn failed_build_keeps_its_errors()
+{
+    let mut session = new_started_session();
+
+    session.build_failed(vec![example_build_error()]);
+
+    assert_eq!([example_build_error()], session.build_errors());
+}
+
+#[test]
+pub fn failed_build_keeps_hyps_of_previous_run_as_stale()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(HypReport::new_fixed(
+        TestHypKind::Hyp(TestHyp::new(hyp_id.clone())),
+        HypState::Passed
+    ));
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+    session.build_failed(vec![example_build_error()]);
+
+    let hyp = session.hyps().get(hyp_id.chain()).unwrap();
+
+    assert_matches!(hyp.state(), HypState::Passed);
+    assert!(hyp.is_stale());
+}
+
+#[test]
+pub fn starting_a_run_after_a_failed_build_clears_build_errors()
+{
+    let mut session = new_started_session();
+
+    session.build_failed(vec![example_build_error()]);
+    session.start_run(RunExtent::AllHyps);
+
+    assert_matches!(session.activity(), Ok(HypState::Running));
+    assert!(session.build_errors().is_empty());
+}
+
+#[test]
+pub fn hyp_that_passed_after_a_retry_is_flaky()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+    session.send_hyp(flaky_hyp(hyp_id.clone()));
+
+    let hyp = session.hyps().get(hyp_id.chain()).unwrap();
+
+    assert_matches!(hyp.state(), HypState::Flaky);
+    assert_eq!(1, hyp.flaky_runs());
+}
+
+#[test]
+pub fn hyp_remembers_how_many_runs_it_was_flaky_in()
+{
+    let mut session = new_started_session();
+
+    let hyp_id = TestId::from("example");
+
+    session.send_hyp(flaky_hyp(hyp_id.clone()));
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+    session.send_hyp(passed_hyp(hyp_id.clone()));
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+    session.send_hyp(HypReport::new_fixed(
+        TestHypKind::Hyp(TestHyp::new(hyp_id.clone())),
+        HypState::Running
+    ));
+    session.send_hyp(flaky_hyp(hyp_id.clone()));
+    session.complete_run();
+
+    assert_eq!(2, session.hyps().get(hyp_id.chain()).unwrap().flaky_runs());
+}
+
+#[test]
+pub fn session_keeps_the_latest_progress_of_a_run()
+{
+    let mut session = new_started_session();
+
+    session.send_progress(RunProgress::new(3));
+
+    let mut progress = RunProgress::new(3);
+    progress.record(HypState::Passed);
+    session.send_progress(progress);
+
+    assert_eq!(Some(&progress), session.progress());
+}
+
+#[test]
+pub fn summary_remains_after_the_run_completed()
+{
+    let mut session = new_started_session();
+
+    let summary = RunSummary {
+        progress: RunProgress {
+            total: 2,
+            passed: 1,
+            failed: 1,
+            skipped: 0,
+            elapsed: Duration::from_millis(4300)
+        },
+        elapsed: Duration::from_millis(4300)
+    };
+
+    session.send_summary(summary);
+    session.complete_run();
+
+    assert_eq!(Some(&summary), session.summary());
+}
+
+#[test]
+pub fn starting_a_run_clears_progress_and_summary_of_the_previous_run()
+{
+    let mut session = new_started_session();
+
+    session.send_progress(RunProgress::new(1));
+    session.send_summary(RunSummary {
+        progress: RunProgress::new(1),
+        elapsed: Duration::from_secs(1)
+    });
+    session.complete_run();
+
+    session.start_run(RunExtent::AllHyps);
+
+    assert_eq!(None, session.progress());
+    assert_eq!(None, session.summary());
+}
+
+fn flaky_hyp(hyp_id: TestId) -> HypReport<TestSession>
+{
+    HypReport::new_fixed(TestHypKind::Hyp(TestHyp::new(hyp_id)), HypState::Flaky)
+}
+
+fn passed_hyp(hyp_id: TestId) -> HypReport<TestSession>
+{
+    HypReport::new_fixed(TestHypKind::Hyp(TestHyp::new(hyp_id)), HypState::Passed)
+}
+
+fn example_build_error() -> CompilerDiagnostic
+{
+    CompilerDiagnostic {
+        message: String::from("mismatched types"),
+        code: Some(String::from("E0308")),
+        level: DiagnosticLevel::Error,
+        spans: Vec::new(),
+        children: Vec::new(),
+        rendered: None
+    }
+}
+
 fn new_started_session() -> TestSession
 {
     let mut session = TestSession::new();
 
-    session.start_run();
+    session.start_run(RunExtent::AllHyps);
 
     session
 }
@@ -0,0 +1,301 @@
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel as CargoDiagnosticLevel};
+use cargo_nextest::cargo_cli::{CargoBuildScopeOptions, CargoOptions};
+use passivate_configuration::run_scope::RunScope;
+use passivate_hyp_names::hyp_id::HypId;
+use passivate_model_bridge::compiler_diagnostic::{CompilerDiagnostic, DiagnosticLevel, DiagnosticSpan};
+use passivate_model_bridge::hyp_session_bridge::SendOutputBridge;
+use passivate_model_bridge::hyp_session_event::CompilationMessage;
+use passivate_model_bridge::output_report::OutputReport;
+
+use crate::hyp_run_error::HypRunError;
+use crate::model::{RustBridge, RustOutput, WorkspaceCompilation};
+
+enum CargoOutput
+{
+    Message(Box<Message>),
+    Stderr(String)
+}
+
+/// Build the test binaries the way nextest does, sending cargo's messages to the session while the build runs
+///
+/// Nextest's own build that follows finds everything up to date. Returns the test binaries cargo built.
+pub(crate) fn build_tests<TTx>(
+    manifest_path: &Utf8Path,
+    cargo_options: &CargoOptions,
+    tx: &mut TTx
+) -> Result<Vec<Utf8PathBuf>, HypRunError>
+where
+    TTx: SendOutputBridge<RustBridge>
+{
+    let mut child = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .arg("test")
+        .arg("--no-run")
+        .arg("--message-format=json")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .args(cargo_args(cargo_options))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    let mut errors = Vec::new();
+    let mut test_binaries = Vec::new();
+
+    std::thread::scope(|scope| {
+        let (output_tx, output_rx) = mpsc::channel();
+        let stderr_tx = output_tx.clone();
+
+        scope.spawn(move || {
+            for message in Message::parse_stream(BufReader::new(stdout)).map_while(Result::ok)
+            {
+                _ = output_tx.send(CargoOutput::Message(Box::new(message)));
+            }
+        });
+
+        scope.spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok)
+            {
+                _ = stderr_tx.send(CargoOutput::Stderr(line));
+            }
+        });
+
+        for output in output_rx
+        {
+            if let CargoOutput::Message(message) = &output
+                && let Some(test_binary) = test_binary(message)
+            {
+                test_binaries.push(test_binary);
+            }
+
+            let output = match output
+            {
+                CargoOutput::Message(message) => compiler_output(*message),
+                CargoOutput::Stderr(line) => cargo_output(&line)
+            };
+
+            if let Some(RustOutput::Diagnostic(diagnostic)) = &output
+                && diagnostic.level == DiagnosticLevel::Error
+            {
+                errors.push(diagnostic.clone());
+            }
+
+            if let Some(output) = output
+            {
+                tx.send_output(OutputReport::new(HypId::empty(), output));
+            }
+        }
+    });
+
+    let status = child.wait()?;
+
+    if status.success()
+    {
+        Ok(test_binaries)
+    }
+    else
+    {
+        Err(HypRunError::BuildFailed {
+            status: status.to_string(),
+            errors
+        })
+    }
+}
+
+/// The directory cargo puts the binaries it builds for the run scope in
+pub(crate) fn profile_dir(target_dir: &Utf8Path, run_scope: &RunScope) -> Utf8PathBuf
+{
+    let mut profile_dir = target_dir.to_path_buf();
+
+    if let Some(target) = &run_scope.target
+    {
+        let target = Utf8Path::new(target);
+
+        // A target given by the path of its specification builds to a directory named after the file
+        let target_name = match target.extension()
+        {
+            Some("json") => target.file_stem(),
+            _ => None
+        };
+
+        profile_dir.push(target_name.unwrap_or(target.as_str()));
+    }
+
+    profile_dir.push(if run_scope.release { "release" } else { "debug" });
+
+    profile_dir
+}
+
+/// Arguments selecting what to build, from the same options nextest builds with so that its build finds everything up
+/// to date
+fn cargo_args(cargo_options: &CargoOptions) -> Vec<String>
+{
+    let CargoBuildScopeOptions {
+        packages,
+        workspace,
+        exclude,
+        all,
+        lib,
+        bin,
+        bins,
+        example,
+        examples,
+        test,
+        tests,
+        bench,
+        benches,
+        all_targets,
+        features,
+        all_features,
+        no_default_features
+    } = &cargo_options.build_scope;
+
+    let mut args = Vec::new();
+
+    let flags = [
+        (*workspace, "--workspace"),
+        (*all, "--all"),
+        (*lib, "--lib"),
+        (*bins, "--bins"),
+        (*examples, "--examples"),
+        (*tests, "--tests"),
+        (*benches, "--benches"),
+        (*all_targets, "--all-targets"),
+        (*all_features, "--all-features"),
+        (*no_default_features, "--no-default-features"),
+        (cargo_options.release, "--release")
+    ];
+
+    args.extend(flags.into_iter().filter(|(set, _)| *set).map(|(_, flag)| String::from(flag)));
+
+    let options = [
+        ("--package", packages),
+        ("--exclude", exclude),
+        ("--bin", bin),
+        ("--example", example),
+        ("--test", test),
+        ("--bench", bench),
+        ("--features", features)
+    ];
+
+    for (option, values) in options
+    {
+        args.extend(values.iter().flat_map(|value| [String::from(option), value.clone()]));
+    }
+
+    if let Some(cargo_profile) = &cargo_options.cargo_profile
+    {
+        args.extend([String::from("--profile"), cargo_profile.clone()]);
+    }
+
+    if let Some(target) = &cargo_options.target
+    {
+        args.extend([String::from("--target"), target.clone()]);
+    }
+
+    if let Some(target_dir) = &cargo_options.target_dir
+    {
+        args.extend([String::from("--target-dir"), target_dir.to_string()]);
+    }
+
+    args
+}
+
+/// The executable of a test target cargo built
+fn test_binary(message: &Message) -> Option<Utf8PathBuf>
+{
+    let Message::CompilerArtifact(artifact) = message
+    else
+    {
+        return None;
+    };
+
+    artifact.profile.test.then(|| artifact.executable.clone()).flatten()
+}
+
+/// A diagnostic from compiling one of the projects
+fn compiler_output(message: Message) -> Option<RustOutput>
+{
+    let Message::CompilerMessage(compiler_message) = message
+    else
+    {
+        return None;
+    };
+
+    Some(RustOutput::Diagnostic(compiler_diagnostic(compiler_message.message)))
+}
+
+fn compiler_diagnostic(diagnostic: Diagnostic) -> CompilerDiagnostic
+{
+    let level = match diagnostic.level
+    {
+        CargoDiagnosticLevel::Error | CargoDiagnosticLevel::Ice => DiagnosticLevel::Error,
+        CargoDiagnosticLevel::Warning => DiagnosticLevel::Warning,
+        CargoDiagnosticLevel::Help => DiagnosticLevel::Help,
+        _ => DiagnosticLevel::Note
+    };
+
+    let spans = diagnostic
+        .spans
+        .into_iter()
+        .map(|span| {
+            DiagnosticSpan {
+                file: Utf8PathBuf::from(span.file_name),
+                line_start: span.line_start,
+                line_end: span.line_end,
+                column_start: span.column_start,
+                column_end: span.column_end,
+                is_primary: span.is_primary,
+                label: span.label,
+                suggested_replacement: span.suggested_replacement
+            }
+        })
+        .collect();
+
+    CompilerDiagnostic {
+        message: diagnostic.message,
+        code: diagnostic.code.map(|code| code.code),
+        level,
+        spans,
+        children: diagnostic.children.into_iter().map(compiler_diagnostic).collect(),
+        rendered: diagnostic.rendered.map(|rendered| rendered.trim_end().to_string())
+    }
+}
+
+/// A line cargo reports about the build of the workspace as a whole, such as which crate is compiling
+fn cargo_output(line: &str) -> Option<RustOutput>
+{
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with("Executable")
+    {
+        return None;
+    }
+
+    let compilation = if line.starts_with("Blocking waiting for file lock")
+    {
+        WorkspaceCompilation::WaitForLock
+    }
+    else if line.starts_with("error")
+    {
+        WorkspaceCompilation::Message(CompilationMessage::new_error(line))
+    }
+    else if line.starts_with("warning")
+    {
+        WorkspaceCompilation::Message(CompilationMessage::new_warning(line))
+    }
+    else
+    {
+        WorkspaceCompilation::Message(CompilationMessage::new_info(line))
+    };
+
+    Some(RustOutput::Workspace(compilation))
+}
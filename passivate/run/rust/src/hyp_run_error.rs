@@ -1,6 +1,10 @@
 use std::io;
 use std::sync::Arc;
 
+use itertools::Itertools;
+use passivate_coverage::coverage_errors::CoverageError;
+use passivate_coverage::coverage_thresholds::CoverageBelowThreshold;
+use passivate_model_bridge::compiler_diagnostic::CompilerDiagnostic;
 use thiserror::Error;
 
 use crate::nextest_error::NextestError;
@@ -14,8 +18,21 @@ pub enum HypRunError
     #[error("nextest error: {0}")]
     Nextest(#[from] Arc<NextestError>),
 
+    #[error("build failed with {status}")]
+    BuildFailed
+    {
+        status: String,
+        errors: Vec<CompilerDiagnostic>
+    },
+
     #[error("guppy error: {0}")]
-    Guppy(#[from] guppy::Error)
+    Guppy(#[from] guppy::Error),
+
+    #[error("{}", .0.iter().join("\n"))]
+    CoverageBelowThreshold(Vec<CoverageBelowThreshold>),
+
+    #[error("coverage thresholds could not be checked: {0}")]
+    CoverageThresholds(CoverageError)
 }
 
 impl From<io::Error> for HypRunError
@@ -1,19 +1,37 @@
+use std::collections::HashSet;
+use std::fs;
 use std::pin::Pin;
 use std::future;
+use std::sync::Arc;
 
+use camino::{Utf8Path, Utf8PathBuf};
+use passivate_configuration::ignored_hyps::IgnoredHyps;
+use passivate_coverage::compute_coverage::ComputeCoverage;
+use passivate_coverage::coverage_bridge::SendCoverageBridge;
+use passivate_coverage::coverage_errors::CoverageError;
+use passivate_coverage::coverage_options::CoverageOptions;
+use passivate_coverage::coverage_status::CoverageStatus;
+use passivate_coverage::coverage_thresholds;
+use passivate_coverage::hyp_coverage::HypCoverage;
+use passivate_hyp_names::hyp_id::HypId;
 use passivate_model_bridge::bridge::Bridge;
 use passivate_model_bridge::hyp_run_request::{HypRunRequest, HypRunRequestKind};
 use passivate_model_bridge::hyp_session_bridge::{
+    BuildFailedBridge,
     CancelRunBridge,
     CompleteRunBridge,
     RunErrorBridge,
     SendHypBridge,
     SendOutputBridge,
+    SendProgressBridge,
+    SendSummaryBridge,
     StartRunBridge
 };
+use passivate_model_bridge::run_extent::RunExtent;
 use tokio_util::sync::CancellationToken;
 
 use crate::hyp_run_error::HypRunError;
+use crate::cargo_build;
 use crate::hyp_runner::{RunHyps, RunHypsOptions};
 use crate::model::RustBridge;
 
@@ -22,37 +40,137 @@ pub trait HypSessionBridge<TBridge: Bridge> = StartRunBridge<TBridge>
     + SendOutputBridge<TBridge>
     + CompleteRunBridge<TBridge>
     + CancelRunBridge<TBridge>
-    + RunErrorBridge<TBridge>;
+    + RunErrorBridge<TBridge>
+    + BuildFailedBridge<TBridge>
+    + SendProgressBridge<TBridge>
+    + SendSummaryBridge<TBridge>;
 
-async fn pending_hyp_run<THypSessionBridge, TRunHyps>(hyp_session_bridge: THypSessionBridge, run_hyps: TRunHyps, cancellation: CancellationToken) -> (THypSessionBridge, TRunHyps)
-where
-    THypSessionBridge: HypSessionBridge<RustBridge>,
-    TRunHyps: RunHyps + Send
+/// Everything a request needs to be handled, handed from one request to the next
+struct HypRunContext<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>
+{
+    hyp_session_bridge: THypSessionBridge,
+    run_hyps: TRunHyps,
+    compute_coverage: Arc<TComputeCoverage>,
+    coverage_bridge: TCoverageBridge,
+    hyp_coverage: HypCoverage
+}
+
+/// The request being handled, which gives its context back once it finished or was cancelled
+type RunningRequest<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge> =
+    Pin<Box<dyn Future<Output = HypRunContext<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>> + Send>>;
+
+async fn pending_hyp_run<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>(
+    context: HypRunContext<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>,
+    cancellation: CancellationToken
+) -> HypRunContext<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>
 {
     cancellation.run_until_cancelled(future::pending::<!>()).await;
 
-    (hyp_session_bridge, run_hyps)
+    context
 }
 
-pub async fn handle_request<THypSessionBridge, TRunHyps>(
+pub async fn handle_request<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>(
     hyp_session_bridge: &mut THypSessionBridge,
     run_hyps: &mut TRunHyps,
+    compute_coverage: &Arc<TComputeCoverage>,
+    coverage_bridge: &mut TCoverageBridge,
+    hyp_coverage: &mut HypCoverage,
     request: HypRunRequest<RustBridge>,
     cancellation: CancellationToken,
 )
 where
     THypSessionBridge: HypSessionBridge<RustBridge>,
-    TRunHyps: RunHyps + Send + Sync + 'static
+    TRunHyps: RunHyps + Send + Sync + 'static,
+    TComputeCoverage: ComputeCoverage + Send + Sync + 'static,
+    TCoverageBridge: SendCoverageBridge
 {
-    hyp_session_bridge.start_run();
+    // Hyps impacted by the changed lines, or none when every hyp has to run
+    let impacted_hyps = match &request.kind
+    {
+        HypRunRequestKind::Impacted { changed_files } => impacted_hyps(hyp_coverage, &request.paths.root, changed_files),
+        _ => None
+    };
+
+    // Coverage of only the impacted hyps would replace the coverage that selected them, so it is kept as it was
+    let keep_coverage = impacted_hyps.is_some();
+
+    if keep_coverage && let HypRunRequestKind::Impacted { changed_files } = &request.kind
+    {
+        // The kept coverage was recorded before these changes, so a later change to the same files runs every hyp
+        for changed_file in changed_files
+        {
+            if let Ok(path) = changed_file.strip_prefix(&request.paths.root)
+            {
+                hyp_coverage.mark_outdated(path.to_path_buf());
+            }
+        }
+    }
+
+    let extent = match &request.kind
+    {
+        HypRunRequestKind::All | HypRunRequestKind::FailedFirst { .. } => RunExtent::AllHyps,
+        HypRunRequestKind::Impacted { .. } if impacted_hyps.is_none() => RunExtent::AllHyps,
+        HypRunRequestKind::Single { .. } | HypRunRequestKind::Affected { .. } | HypRunRequestKind::Impacted { .. } =>
+        {
+            RunExtent::SomeHyps
+        }
+    };
+
+    hyp_session_bridge.start_run(extent);
 
-    let target_dir = request.passivate_directory().join("target");
+    let passivate_directory = request.passivate_directory();
+
+    // Instrumented builds go to their own target directory, so toggling coverage does not invalidate the regular build
+    let coverage_target_dir = passivate_directory.join("coverage_target");
+
+    let coverage_options = request.configuration.coverage_enabled.then(|| CoverageOptions {
+        workspace_path: request.paths.root.clone(),
+        output_path: passivate_directory.join("coverage"),
+        binary_path: cargo_build::profile_dir(&coverage_target_dir, &request.configuration.run_scope)
+    });
+
+    let target_dir = if coverage_options.is_some()
+    {
+        coverage_target_dir
+    }
+    else
+    {
+        passivate_directory.join("target")
+    };
+
+    match &coverage_options
+    {
+        Some(_) if keep_coverage =>
+        {}
+        Some(coverage_options) =>
+        {
+            coverage_bridge.send_coverage(CoverageStatus::Preparing);
+
+            if let Err(error) = compute_coverage.clean_coverage_output(coverage_options)
+            {
+                log::warn!("{error}");
+            }
+        }
+        None => coverage_bridge.send_coverage(CoverageStatus::Disabled)
+    };
+
+    let ignored_hyps = match &request.kind
+    {
+        // A single hyp is run on demand, which it should be even when it is ignored
+        HypRunRequestKind::Single { .. } => IgnoredHyps::Include,
+        _ => request.configuration.ignored_hyps
+    };
 
     let run_hyps_options = RunHypsOptions {
       manifest_dir: request.paths.root,
-      coverage_dir: None,
+      coverage_dir: coverage_options.as_ref().map(|coverage_options| coverage_options.output_path.clone()),
       target_dir,
-      update_snapshots: false
+      update_snapshots: false,
+      retries: request.configuration.retries,
+      ignored_hyps,
+      timeouts: request.configuration.timeouts.clone(),
+      nextest_profile: request.configuration.nextest_profile.clone(),
+      run_scope: request.configuration.run_scope.clone()
     };
 
     let task: Pin<Box<dyn Future<Output = Result<(), HypRunError>> + Send>> = match request.kind
@@ -62,6 +180,19 @@ where
         {
             Box::pin(run_hyps.run_hyp( hyp_id, &run_hyps_options,  hyp_session_bridge))
         }
+        HypRunRequestKind::Affected { changed_files } =>
+        {
+            Box::pin(run_hyps.run_affected(changed_files, &run_hyps_options, hyp_session_bridge))
+        }
+        HypRunRequestKind::Impacted { changed_files: _ } => match impacted_hyps
+        {
+            Some(hyp_ids) => Box::pin(run_hyps.run_hyp_set(hyp_ids, &run_hyps_options, hyp_session_bridge)),
+            None => Box::pin(run_hyps.run_hyps(&run_hyps_options, hyp_session_bridge))
+        },
+        HypRunRequestKind::FailedFirst { failed_hyps } =>
+        {
+            Box::pin(run_hyps.run_failed_first(failed_hyps, &run_hyps_options, hyp_session_bridge))
+        }
     };
 
     let result = cancellation.run_until_cancelled(task).await;
@@ -70,11 +201,85 @@ where
     {
         Some(Ok(_)) =>
         {
-            hyp_session_bridge.complete_run();
+            let mut threshold_error = None;
+
+            if let Some(coverage_options) = coverage_options.as_ref().filter(|_| !keep_coverage)
+            {
+                coverage_bridge.send_coverage(CoverageStatus::Running);
+
+                let coverage = match compute_cancellable(
+                    compute_coverage,
+                    coverage_options,
+                    &cancellation,
+                    TComputeCoverage::compute_coverage
+                )
+                .await
+                {
+                    Ok(coverage) => coverage,
+                    Err(CoverageError::Cancelled) => CoverageStatus::Cancelled,
+                    Err(error) => CoverageStatus::Error(error.to_string())
+                };
+
+                if let CoverageStatus::Done(covdir) = &coverage
+                {
+                    match compute_cancellable(
+                        compute_coverage,
+                        coverage_options,
+                        &cancellation,
+                        TComputeCoverage::compute_hyp_coverage
+                    )
+                    .await
+                    {
+                        Ok(coverage) => *hyp_coverage = coverage,
+                        Err(CoverageError::Cancelled) =>
+                        {
+                            coverage_bridge.send_coverage(CoverageStatus::Cancelled);
+                            hyp_session_bridge.cancel_run();
+                            return;
+                        }
+                        Err(error) => log::warn!("{error}")
+                    };
+
+                    let thresholds = &request.configuration.coverage_thresholds;
+
+                    match coverage_thresholds::check_thresholds(covdir, thresholds, &coverage_options.workspace_path)
+                    {
+                        Ok(below) if below.is_empty() => (),
+                        Ok(below) => threshold_error = Some(HypRunError::CoverageBelowThreshold(below)),
+                        Err(error) => threshold_error = Some(HypRunError::CoverageThresholds(error))
+                    };
+                }
+
+                let cancelled = coverage == CoverageStatus::Cancelled;
+
+                coverage_bridge.send_coverage(coverage);
+
+                // The run ends with its coverage, so a run whose coverage was cancelled was cancelled itself
+                if cancelled
+                {
+                    hyp_session_bridge.cancel_run();
+                    return;
+                }
+            }
+
+            match threshold_error
+            {
+                Some(threshold_error) => hyp_session_bridge.run_error(threshold_error),
+                None => hyp_session_bridge.complete_run()
+            }
         }
         Some(Err(test_error)) =>
         {
-            hyp_session_bridge.run_error(test_error);
+            if coverage_options.is_some() && !keep_coverage
+            {
+                coverage_bridge.send_coverage(CoverageStatus::Error(String::from("coverage was not computed - the hyp run failed")));
+            }
+
+            match test_error
+            {
+                HypRunError::BuildFailed { errors, .. } => hyp_session_bridge.build_failed(errors),
+                test_error => hyp_session_bridge.run_error(test_error)
+            }
         }
         None =>
         {
@@ -83,19 +288,80 @@ where
     };
 }
 
-async fn handle_request_take<THypSessionBridge, TRunHyps>(
-    mut hyp_session_bridge: THypSessionBridge,
-    mut run_hyps: TRunHyps,
+/// Hyps whose recorded coverage executed the changed lines, or none when every hyp has to run because that is unknown
+/// or a changed file has no lines any hyp executed
+fn impacted_hyps(hyp_coverage: &HypCoverage, workspace_path: &Utf8Path, changed_files: &[Utf8PathBuf]) -> Option<Vec<HypId>>
+{
+    let mut impacted = HashSet::new();
+
+    for changed_file in changed_files
+    {
+        // Only covered sources can be traced to hyps, other files such as manifests may affect any hyp
+        if changed_file.extension() != Some("rs")
+        {
+            return None;
+        }
+
+        let path = changed_file.strip_prefix(workspace_path).ok()?;
+        let content = fs::read_to_string(changed_file).ok()?;
+
+        let impacted_by_file = hyp_coverage.hyps_impacted_by(path, &content)?;
+
+        // Lines no hyp executed may be new hyps themselves, which only a run of every hyp finds
+        if impacted_by_file.is_empty()
+        {
+            return None;
+        }
+
+        impacted.extend(impacted_by_file);
+    }
+
+    Some(impacted.into_iter().collect())
+}
+
+/// Coverage tools block until they finish, so they run off the async workers to let a new request cancel them
+async fn compute_cancellable<TComputeCoverage, TOutput>(
+    compute_coverage: &Arc<TComputeCoverage>,
+    options: &CoverageOptions,
+    cancellation: &CancellationToken,
+    compute: impl FnOnce(&TComputeCoverage, &CoverageOptions, &CancellationToken) -> Result<TOutput, CoverageError> + Send + 'static
+) -> Result<TOutput, CoverageError>
+where
+    TComputeCoverage: ComputeCoverage + Send + Sync + 'static,
+    TOutput: Send + 'static
+{
+    let compute_coverage = Arc::clone(compute_coverage);
+    let options = options.clone();
+    let cancellation = cancellation.clone();
+
+    tokio::task::spawn_blocking(move || compute(&compute_coverage, &options, &cancellation))
+        .await
+        .unwrap_or_else(|join_error| std::panic::resume_unwind(join_error.into_panic()))
+}
+
+async fn handle_request_take<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>(
+    mut context: HypRunContext<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>,
     request: HypRunRequest<RustBridge>,
     cancellation: CancellationToken,
-) -> (THypSessionBridge, TRunHyps)
+) -> HypRunContext<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>
 where
     THypSessionBridge: HypSessionBridge<RustBridge>,
-    TRunHyps: RunHyps + Send + Sync + 'static
+    TRunHyps: RunHyps + Send + Sync + 'static,
+    TComputeCoverage: ComputeCoverage + Send + Sync + 'static,
+    TCoverageBridge: SendCoverageBridge
 {
-    handle_request(&mut hyp_session_bridge, &mut run_hyps, request, cancellation).await;
+    handle_request(
+        &mut context.hyp_session_bridge,
+        &mut context.run_hyps,
+        &context.compute_coverage,
+        &mut context.coverage_bridge,
+        &mut context.hyp_coverage,
+        request,
+        cancellation
+    )
+    .await;
 
-    (hyp_session_bridge, run_hyps)
+    context
 }
 
 pub fn build_tokio_runtime() -> tokio::runtime::Runtime
@@ -107,22 +373,34 @@ pub fn build_tokio_runtime() -> tokio::runtime::Runtime
         .unwrap()
 }
 
-pub fn spawn_hyp_run_future<THypSessionBridge, TRunHyps>(
+pub fn spawn_hyp_run_future<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge>(
     runtime: &tokio::runtime::Runtime,
     mut hyp_run_trigger_rx: tokio::sync::mpsc::Receiver<HypRunRequest<RustBridge>>,
     hyp_session_bridge: THypSessionBridge,
-    run_hyps: TRunHyps
+    run_hyps: TRunHyps,
+    compute_coverage: TComputeCoverage,
+    coverage_bridge: TCoverageBridge
 )
 -> tokio::task::JoinHandle<()>
 where
     THypSessionBridge: HypSessionBridge<RustBridge>,
-    TRunHyps: RunHyps + Send + Sync + 'static
+    TRunHyps: RunHyps + Send + Sync + 'static,
+    TComputeCoverage: ComputeCoverage + Send + Sync + 'static,
+    TCoverageBridge: SendCoverageBridge
 {
     runtime.spawn(async move {
         let mut cancellation = tokio_util::sync::CancellationToken::new();
 
-        let mut running_request: Pin<Box<dyn Future<Output = (THypSessionBridge, TRunHyps)> + Send>> =
-            Box::pin(pending_hyp_run(hyp_session_bridge, run_hyps, cancellation.child_token()));
+        let context = HypRunContext {
+            hyp_session_bridge,
+            run_hyps,
+            compute_coverage: Arc::new(compute_coverage),
+            coverage_bridge,
+            hyp_coverage: HypCoverage::default()
+        };
+
+        let mut running_request: RunningRequest<THypSessionBridge, TRunHyps, TComputeCoverage, TCoverageBridge> =
+            Box::pin(pending_hyp_run(context, cancellation.child_token()));
 
         loop
         {
@@ -135,9 +413,9 @@ where
                         {
                             // New request, cancel a running request first and retrieve the context, then start the handling the new request
                             cancellation.cancel();
-                            let (hyp_session_bridge, run_hyps) = running_request.await;
+                            let context = running_request.await;
                             cancellation = CancellationToken::new();
-                            running_request = Box::pin(handle_request_take(hyp_session_bridge, run_hyps, request, cancellation.child_token()));
+                            running_request = Box::pin(handle_request_take(context, request, cancellation.child_token()));
                         },
                         None => {
                             // Channel closed, cancel a running request
@@ -148,9 +426,9 @@ where
                     };
                 }
 
-                (hyp_session_bridge, run_hyps) = running_request.as_mut() => {
+                context = running_request.as_mut() => {
                     // A request completed
-                    running_request = Box::pin(pending_hyp_run(hyp_session_bridge, run_hyps, cancellation.child_token()));
+                    running_request = Box::pin(pending_hyp_run(context, cancellation.child_token()));
                 }
             };
         };
@@ -1,40 +1,55 @@
-use std::collections::BTreeSet;
-use std::fs;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{fs, io};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use cargo_nextest::dispatch::helpers::acquire_graph_data;
 use cargo_nextest::output::{Color, OutputContext};
 use guppy::graph::PackageGraph;
 use itertools::Itertools;
 use nextest_filtering::{Filterset, FiltersetKind, ParseContext};
-use nextest_runner::cargo_config::{CargoConfigs, EnvironmentMap};
+use nextest_metadata::MismatchReason;
+use nextest_runner::RustcCli;
+use nextest_runner::cargo_config::{CargoConfigs, EnvironmentMap, TargetTriple};
 use nextest_runner::config::core::{NextestConfig, get_num_cpus};
-use nextest_runner::config::elements::MaxFail;
+use nextest_runner::config::elements::{MaxFail, RetryPolicy};
 use nextest_runner::double_spawn::DoubleSpawnInfo;
 use nextest_runner::input::InputHandlerKind;
-use nextest_runner::list::{RustTestArtifact, TestExecuteContext, TestList};
-use nextest_runner::platform::BuildPlatforms;
-use nextest_runner::reporter::FinalStatusLevel;
-use nextest_runner::reporter::events::{ChildExecutionOutputDescription, TestEvent};
+use nextest_runner::list::{RustTestArtifact, TestExecuteContext, TestInstanceId, TestList};
+use nextest_runner::platform::{BuildPlatforms, HostPlatform, PlatformLibdir, TargetPlatform};
+use nextest_runner::reporter::events::{
+    ChildExecutionOutputDescription,
+    ChildOutputDescription,
+    ExecutionDescription,
+    ExecutionResultDescription,
+    RunFinishedStats,
+    TestEvent
+};
 use nextest_runner::reuse_build::PathMapper;
 use nextest_runner::run_mode::NextestRunMode;
 use nextest_runner::runner::TestRunnerBuilder;
 use nextest_runner::signal::SignalHandlerKind;
 use nextest_runner::target_runner::TargetRunner;
 use nextest_runner::test_filter::{FilterBound, RunIgnored, TestFilter, TestFilterPatterns};
+use passivate_configuration::hyp_timeouts::HypTimeouts;
+use passivate_configuration::ignored_hyps::IgnoredHyps;
+use passivate_configuration::run_scope::RunScope;
+use passivate_coverage::hyp_profiles::HypProfileRecorder;
+use passivate_coverage::test_binaries::TestBinaries;
 use passivate_hyp_names::hyp_id::HypId;
 use passivate_hyp_names::hyp_name_strategy::HypNameStrategy;
 use passivate_model_bridge::hyp_report::HypReport;
-use passivate_model_bridge::hyp_session_bridge::{SendHypBridge, SendOutputBridge};
+use passivate_model_bridge::hyp_session_bridge::{SendHypBridge, SendOutputBridge, SendProgressBridge, SendSummaryBridge};
 use passivate_model_bridge::hyp_session_event::{ConsoleOutput, ConsoleOutputKind};
 use passivate_model_bridge::hyp_state::HypState;
 use passivate_model_bridge::output_report::OutputReport;
+use passivate_model_bridge::run_progress::{RunProgress, RunSummary};
 
 use crate::hyp_run_error::HypRunError;
 use crate::model::{RustBridge, RustHyp, RustOutput};
-use crate::nextest_cargo_options;
 use crate::nextest_error::NextestError;
+use crate::{cargo_build, nextest_cargo_options, panic_failure, profile_runner};
 
 #[derive(bon::Builder)]
 pub struct RunHypsOptions
@@ -42,7 +57,14 @@ pub struct RunHypsOptions
     pub manifest_dir: Utf8PathBuf,
     pub target_dir: Utf8PathBuf,
     pub coverage_dir: Option<Utf8PathBuf>,
-    pub update_snapshots: bool
+    pub update_snapshots: bool,
+    /// How many times a failing hyp is retried, none leaves it to the nextest profile
+    pub retries: Option<u32>,
+    pub ignored_hyps: IgnoredHyps,
+    pub timeouts: HypTimeouts,
+    /// The nextest profile to run with, none uses the default profile
+    pub nextest_profile: Option<String>,
+    pub run_scope: RunScope
 }
 
 #[mockall::automock]
@@ -51,11 +73,112 @@ pub trait RunHyps
 {
     async fn run_hyps<TTx>(&mut self, options: &RunHypsOptions, tx: &mut TTx) -> Result<(), HypRunError>
     where
-        TTx: SendHypBridge<RustBridge> + SendOutputBridge<RustBridge>;
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>;
 
     async fn run_hyp<TTx>(&mut self, hyp_id: HypId, options: &RunHypsOptions, tx: &mut TTx) -> Result<(), HypRunError>
     where
-        TTx: SendHypBridge<RustBridge> + SendOutputBridge<RustBridge>;
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>;
+
+    async fn run_hyp_set<TTx>(
+        &mut self,
+        hyp_ids: Vec<HypId>,
+        options: &RunHypsOptions,
+        tx: &mut TTx
+    ) -> Result<(), HypRunError>
+    where
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>;
+
+    /// Run the hyps of the packages owning the changed files and of their reverse dependencies
+    async fn run_affected<TTx>(
+        &mut self,
+        changed_files: Vec<Utf8PathBuf>,
+        options: &RunHypsOptions,
+        tx: &mut TTx
+    ) -> Result<(), HypRunError>
+    where
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>;
+
+    /// Run the hyps that failed before, then every other hyp
+    async fn run_failed_first<TTx>(
+        &mut self,
+        failed_hyp_ids: Vec<HypId>,
+        options: &RunHypsOptions,
+        tx: &mut TTx
+    ) -> Result<(), HypRunError>
+    where
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>;
+}
+
+/// The hyps a run is restricted to
+enum HypSelection
+{
+    All,
+    Named(Vec<String>),
+    AllExcept(Vec<String>),
+    AffectedBy(Vec<Utf8PathBuf>)
+}
+
+impl HypSelection
+{
+    /// Nextest filtersets selecting the hyps, where none selects the default set
+    fn filtersets(self, graph: &PackageGraph) -> Vec<String>
+    {
+        match self
+        {
+            HypSelection::All => Vec::new(),
+            HypSelection::Named(names) => names.into_iter().map(|name| format!("test(={name})")).collect(),
+            HypSelection::AllExcept(names) if names.is_empty() => Vec::new(),
+            HypSelection::AllExcept(names) =>
+            {
+                let excluded = names.into_iter().map(|name| format!("test(={name})")).join(" | ");
+
+                vec![format!("not ({excluded})")]
+            }
+            HypSelection::AffectedBy(changed_files) =>
+            {
+                let mut packages = BTreeSet::new();
+
+                for changed_file in &changed_files
+                {
+                    match owning_package(graph, changed_file)
+                    {
+                        Some(package) => packages.insert(package),
+                        // A change outside of any package, such as to the workspace manifest, may affect everything
+                        None => return Vec::new()
+                    };
+                }
+
+                packages.into_iter().map(|package| format!("rdeps(={package})")).collect()
+            }
+        }
+    }
+}
+
+/// The workspace package whose directory most closely contains the file
+fn owning_package<'g>(graph: &'g PackageGraph, file: &Utf8Path) -> Option<&'g str>
+{
+    graph
+        .workspace()
+        .iter()
+        .filter_map(|package| Some((package.manifest_path().parent()?, package.name())))
+        .filter(|(directory, _)| file.starts_with(directory))
+        .max_by_key(|(directory, _)| directory.as_str().len())
+        .map(|(_, name)| name)
 }
 
 #[derive(Clone)]
@@ -66,26 +189,95 @@ impl RunHyps for HypRunner
 {
     async fn run_hyps<TTx>(&mut self, options: &RunHypsOptions, tx: &mut TTx) -> Result<(), HypRunError>
     where
-        TTx: SendHypBridge<RustBridge> + SendOutputBridge<RustBridge>
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>
     {
-        let filter = vec![];
-        self.run_hyps_with_options(options, filter, tx).await
+        self.run_hyps_with_options(options, vec![HypSelection::All], tx).await
     }
 
     async fn run_hyp<TTx>(&mut self, hyp_id: HypId, options: &RunHypsOptions, tx: &mut TTx) -> Result<(), HypRunError>
     where
-        TTx: SendHypBridge<RustBridge> + SendOutputBridge<RustBridge>
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>
     {
         let strategy = HypNameStrategy::QualifiedWithoutCrate {
             separator: "::".to_string()
         };
 
-        let filter = vec![hyp_id.name(&strategy).to_string()];
+        let selection = vec![HypSelection::Named(vec![hyp_id.name(&strategy).to_string()])];
 
-        let result = self.run_hyps_with_options(options, filter, tx).await;
+        let result = self.run_hyps_with_options(options, selection, tx).await;
 
         result
     }
+
+    async fn run_hyp_set<TTx>(&mut self, hyp_ids: Vec<HypId>, options: &RunHypsOptions, tx: &mut TTx) -> Result<(), HypRunError>
+    where
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>
+    {
+        self.run_hyps_with_options(options, vec![HypSelection::Named(filter_names(&hyp_ids))], tx)
+            .await
+    }
+
+    async fn run_affected<TTx>(
+        &mut self,
+        changed_files: Vec<Utf8PathBuf>,
+        options: &RunHypsOptions,
+        tx: &mut TTx
+    ) -> Result<(), HypRunError>
+    where
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>
+    {
+        self.run_hyps_with_options(options, vec![HypSelection::AffectedBy(changed_files)], tx)
+            .await
+    }
+
+    async fn run_failed_first<TTx>(
+        &mut self,
+        failed_hyp_ids: Vec<HypId>,
+        options: &RunHypsOptions,
+        tx: &mut TTx
+    ) -> Result<(), HypRunError>
+    where
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>
+    {
+        if failed_hyp_ids.is_empty()
+        {
+            return self.run_hyps(options, tx).await;
+        }
+
+        let failed_names = filter_names(&failed_hyp_ids);
+
+        let passes = vec![
+            HypSelection::Named(failed_names.clone()),
+            HypSelection::AllExcept(failed_names),
+        ];
+
+        self.run_hyps_with_options(options, passes, tx).await
+    }
+}
+
+/// Names of hyps as matched by nextest's 'test()' filterset
+fn filter_names(hyp_ids: &[HypId]) -> Vec<String>
+{
+    let strategy = HypNameStrategy::QualifiedWithoutCrate {
+        separator: "::".to_string()
+    };
+
+    hyp_ids.iter().map(|hyp_id| hyp_id.name(&strategy).to_string()).collect()
 }
 
 impl HypRunner
@@ -93,11 +285,14 @@ impl HypRunner
     async fn run_hyps_with_options<TTx>(
         &mut self,
         options: &RunHypsOptions,
-        filter: Vec<String>,
+        passes: Vec<HypSelection>,
         tx: &mut TTx
     ) -> Result<(), HypRunError>
     where
-        TTx: SendHypBridge<RustBridge> + SendOutputBridge<RustBridge>
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>
     {
         if let Some(coverage_dir) = &options.coverage_dir
         {
@@ -117,7 +312,7 @@ impl HypRunner
             }
         }
 
-        let result = self.run_hyps_internal(options, filter, tx).await;
+        let result = self.run_hyps_internal(options, passes, tx).await;
 
         unsafe {
             std::env::remove_var("RUSTFLAGS");
@@ -131,22 +326,31 @@ impl HypRunner
     async fn run_hyps_internal<TTx>(
         &mut self,
         options: &RunHypsOptions,
-        filter: Vec<String>,
+        passes: Vec<HypSelection>,
         tx: &mut TTx
     ) -> Result<(), HypRunError>
     where
-        TTx: SendHypBridge<RustBridge> + SendOutputBridge<RustBridge>
+        TTx: SendHypBridge<RustBridge>
+            + SendOutputBridge<RustBridge>
+            + SendProgressBridge<RustBridge>
+            + SendSummaryBridge<RustBridge>
     {
         log::info!("Starting test run");
 
         std::thread::scope(|scope| {
             scope
                 .spawn(move || {
-                    let cargo_options = nextest_cargo_options::cargo_options()
-                        .target_dir(options.target_dir.clone())
-                        .call();
+                    let cargo_options = nextest_cargo_options::run_scope_options(&options.run_scope, &options.target_dir);
+
+                    // With coverage, test processes are started through passivate to give each its own profile directory
+                    let cli_configs = match &options.coverage_dir
+                    {
+                        Some(coverage_dir) => vec![profile_runner::runner_config(coverage_dir)?],
+                        None => Vec::new()
+                    };
+                    let cargo_configs = CargoConfigs::new(cli_configs).map_err(NextestError::CargoConfig)?;
 
-                    let build_platforms = BuildPlatforms::new_with_no_target().map_err(NextestError::HostPlatformDetect)?;
+                    let build_platforms = build_platforms(&cargo_configs, options.run_scope.target.as_deref())?;
 
                     let output_context = OutputContext {
                         verbose: false,
@@ -171,18 +375,28 @@ impl HypRunner
                     log::info!("Completed 'metadata'");
 
                     let parse_context = ParseContext::new(&graph);
-                    let config_file = None;
+                    let profile_name = options.nextest_profile.as_deref().unwrap_or(NextestConfig::DEFAULT_PROFILE);
+                    // Without a config file nextest reads the project's own .config/nextest.toml
+                    let config_file =
+                        timeouts_config_file(&options.timeouts, profile_name, &options.manifest_dir, &options.target_dir)?;
                     let tool_config_files = Vec::new();
                     let experimental = BTreeSet::new();
                     let nextest_config = NextestConfig::from_sources(
                         options.manifest_dir.as_path(),
                         &parse_context,
-                        config_file,
+                        config_file.as_deref(),
                         tool_config_files,
                         &experimental
                     )
                     .map_err(NextestError::ConfigParse)?;
 
+                    let test_binaries = cargo_build::build_tests(&manifest_path, &cargo_options, tx)?;
+
+                    if let Some(coverage_dir) = &options.coverage_dir
+                    {
+                        TestBinaries::new(test_binaries).save(coverage_dir)?;
+                    }
+
                     let binary_list = cargo_options
                         .compute_binary_list("test", &graph, Some(&manifest_path), output_context, build_platforms.clone())
                         .map_err(NextestError::Expected)?;
@@ -200,96 +414,136 @@ impl HypRunner
                     .map_err(NextestError::FromMessages)?;
 
                     let double_spawn = DoubleSpawnInfo::disabled();
-                    let target_runner = TargetRunner::empty();
+                    let target_runner =
+                        TargetRunner::new(&cargo_configs, &build_platforms).map_err(NextestError::TargetRunner)?;
 
-                    let profile = nextest_config
-                        .profile(NextestConfig::DEFAULT_PROFILE)
-                        .map_err(NextestError::ProfileNotFound)?;
+                    let profile = nextest_config.profile(profile_name).map_err(NextestError::ProfileNotFound)?;
 
                     let profile = profile.apply_build_platforms(&build_platforms);
 
                     let context = TestExecuteContext {
-                        profile_name: NextestConfig::DEFAULT_PROFILE,
+                        profile_name,
                         double_spawn: &double_spawn,
                         target_runner: &target_runner
                     };
 
-                    let test_filter =
-                        if filter.is_empty()
-                        {
-                            TestFilter::default_set(NextestRunMode::Test, RunIgnored::Default)
-                        }
-                        else
-                        {
-                            let patterns = TestFilterPatterns::new(Vec::new());
+                    let run_ignored = match options.ignored_hyps
+                    {
+                        IgnoredHyps::Skip => RunIgnored::Default,
+                        IgnoredHyps::Only => RunIgnored::Only,
+                        IgnoredHyps::Include => RunIgnored::All
+                    };
 
-                            let mut filter_sets = vec![];
+                    // Passes run one after another from the same build, as one run with one progress and summary
+                    let mut test_lists = Vec::new();
 
-                            for pattern in filter
-                            {
-                                let filterset =
-                                    Filterset::parse(format!("test(={})", pattern), &parse_context, FiltersetKind::Test)
-                                        .map_err(|error| {
-                                            error.errors.into_iter().next().map_or_else(
-                                                || NextestError::UnknownFiltersetParse,
-                                                NextestError::FiltersetParse
-                                            )
-                                        })?;
-                                filter_sets.push(filterset);
-                            }
+                    for selection in passes
+                    {
+                        let test_filter = test_filter(selection.filtersets(&graph), run_ignored, &parse_context)?;
+
+                        let env = EnvironmentMap::new(&cargo_configs);
+
+                        let partitioner_builder = None;
+                        let test_list = TestList::new(
+                            &context,
+                            artifacts.iter().cloned(),
+                            rust_build_meta.clone(),
+                            &test_filter,
+                            partitioner_builder,
+                            options.manifest_dir.clone(),
+                            env,
+                            &profile,
+                            FilterBound::DefaultSet,
+                            get_num_cpus()
+                        )
+                        .map_err(NextestError::CreateTestList)?;
 
-                            TestFilter::new(NextestRunMode::Test, RunIgnored::Default, patterns, filter_sets)
-                                .map_err(NextestError::TestFilterBuild)?
-                        };
-
-                    let cli_configs: Vec<String> = Vec::new();
-                    let cargo_configs = CargoConfigs::new(cli_configs.into_iter()).map_err(NextestError::CargoConfig)?;
-
-                    let env = EnvironmentMap::new(&cargo_configs);
-
-                    let partitioner_builder = None;
-                    let test_list = TestList::new(
-                        &context,
-                        artifacts.into_iter(),
-                        rust_build_meta,
-                        &test_filter,
-                        partitioner_builder,
-                        options.manifest_dir.clone(),
-                        env,
-                        &profile,
-                        FilterBound::DefaultSet,
-                        get_num_cpus()
-                    )
-                    .map_err(NextestError::CreateTestList)?;
+                        test_lists.push(test_list);
+                    }
 
-                    let mut runner_builder = TestRunnerBuilder::default();
-                    runner_builder.set_max_fail(MaxFail::from_fail_fast(false));
+                    // Every hyp about to run is known up front, so the session can list it before it finishes
+                    for test_instance in test_lists
+                        .iter()
+                        .flat_map(TestList::iter_tests)
+                        .filter(|test| test.test_info.filter_match.is_match())
+                    {
+                        let hyp_info = RustHyp::new_single(nextest_hyp_id(&test_instance.id()));
+                        tx.send_hyp(HypReport::new_derived(hyp_info));
+                    }
 
-                    let runner = runner_builder
-                        .build(
-                            &test_list,
-                            &profile,
-                            vec![], // we aren't testing CLI args at the moment
-                            SignalHandlerKind::Noop,
-                            InputHandlerKind::Noop,
-                            DoubleSpawnInfo::disabled(),
-                            TargetRunner::empty()
-                        )
-                        .map_err(NextestError::TestRunnerBuild)?;
+                    let mut progress = RunProgress::new(test_lists.iter().map(TestList::run_count).sum());
+                    tx.send_progress(progress);
 
-                    runner
-                        .execute(|test_event| {
-                            match test_event
-                            {
-                                nextest_runner::reporter::events::ReporterEvent::Tick =>
-                                {}
-                                nextest_runner::reporter::events::ReporterEvent::Test(test_event) =>
+                    let started = Instant::now();
+
+                    // The counts nextest reports for each pass add up to those of the run
+                    let mut summary = RunSummary {
+                        progress: RunProgress::default(),
+                        elapsed: Duration::ZERO
+                    };
+
+                    // One recorder spans every pass, so the profiles of a pass are not lost to the next one
+                    let mut profile_recorder = options.coverage_dir.as_deref().map(HypProfileRecorder::start);
+
+                    // Every pass skips the same ignored hyps, which count once for the run
+                    let mut ignored = HashSet::new();
+
+                    for test_list in &test_lists
+                    {
+                        let mut runner_builder = TestRunnerBuilder::default();
+                        runner_builder.set_max_fail(MaxFail::from_fail_fast(false));
+
+                        if let Some(retries) = options.retries
+                        {
+                            runner_builder.set_retries(RetryPolicy::new_without_delay(retries));
+                        }
+
+                        let runner = runner_builder
+                            .build(
+                                test_list,
+                                &profile,
+                                vec![], // we aren't testing CLI args at the moment
+                                SignalHandlerKind::Noop,
+                                InputHandlerKind::Noop,
+                                DoubleSpawnInfo::disabled(),
+                                target_runner.clone()
+                            )
+                            .map_err(NextestError::TestRunnerBuild)?;
+
+                        runner
+                            .execute(|test_event| {
+                                match test_event
                                 {
-                                    process_nextest_event(tx, *test_event)
-                                }
-                            };
-                        })
-                        .map_err(NextestError::TestRunnerExecute)?;
+                                    nextest_runner::reporter::events::ReporterEvent::Tick =>
+                                    {}
+                                    nextest_runner::reporter::events::ReporterEvent::Test(test_event) =>
+                                    {
+                                        process_nextest_event(
+                                            tx,
+                                            &mut profile_recorder,
+                                            &mut progress,
+                                            &mut ignored,
+                                            started,
+                                            &mut summary,
+                                            *test_event
+                                        )
+                                    }
+                                };
+                            })
+                            .map_err(NextestError::TestRunnerExecute)?;
+                    }
+
+                    if let Some(profile_recorder) = profile_recorder
+                    {
+                        profile_recorder.finish()?;
+                    }
+
+                    // Nextest also counts hyps a pass filtered out as skipped, so the ignored hyps counted along the way are
+                    // used instead
+                    summary.progress.skipped = progress.skipped;
+                    summary.progress.elapsed = summary.elapsed;
+
+                    tx.send_summary(summary);
 
                     log::info!("Completed test run");
 
@@ -301,28 +555,166 @@ impl HypRunner
     }
 }
 
-fn process_nextest_event<TTx>(tx: &mut TTx, test_event: TestEvent<'_>)
-where
-    TTx: SendHypBridge<RustBridge> + SendOutputBridge<RustBridge>
+/// The filter selecting the hyps of the filtersets, where none selects the default set
+fn test_filter(
+    filtersets: Vec<String>,
+    run_ignored: RunIgnored,
+    parse_context: &ParseContext<'_>
+) -> Result<TestFilter, HypRunError>
+{
+    if filtersets.is_empty()
+    {
+        return Ok(TestFilter::default_set(NextestRunMode::Test, run_ignored));
+    }
+
+    let patterns = TestFilterPatterns::new(Vec::new());
+
+    let mut filter_sets = vec![];
+
+    for filterset in filtersets
+    {
+        let filterset = Filterset::parse(filterset, parse_context, FiltersetKind::Test).map_err(|error| {
+            error
+                .errors
+                .into_iter()
+                .next()
+                .map_or_else(|| NextestError::UnknownFiltersetParse, NextestError::FiltersetParse)
+        })?;
+        filter_sets.push(filterset);
+    }
+
+    let test_filter =
+        TestFilter::new(NextestRunMode::Test, run_ignored, patterns, filter_sets).map_err(NextestError::TestFilterBuild)?;
+
+    Ok(test_filter)
+}
+
+/// The host and the target the hyps are built for, which nextest needs to pick the target's runner and configuration
+fn build_platforms(cargo_configs: &CargoConfigs, target: Option<&str>) -> Result<BuildPlatforms, NextestError>
+{
+    let host = HostPlatform::detect(PlatformLibdir::from_rustc_stdout(RustcCli::print_host_libdir().read()))?;
+
+    let target = TargetTriple::find(cargo_configs, target, &host.platform)?.map(|triple| {
+        let libdir = PlatformLibdir::from_rustc_stdout(RustcCli::print_target_libdir(&triple).read());
+        TargetPlatform::new(triple, libdir)
+    });
+
+    Ok(BuildPlatforms { host, target })
+}
+
+/// Nextest only reads timeouts from its configuration files, and the project's own would take precedence over a tool
+/// config file, so they are set on the selected profile of a copy of the project's configuration that nextest reads instead
+fn timeouts_config_file(
+    timeouts: &HypTimeouts,
+    profile_name: &str,
+    manifest_dir: &Utf8Path,
+    target_dir: &Utf8Path
+) -> Result<Option<Utf8PathBuf>, HypRunError>
+{
+    if timeouts.is_empty()
+    {
+        return Ok(None);
+    }
+
+    let project_config_file = manifest_dir.join(NextestConfig::CONFIG_PATH);
+
+    let mut config = match fs::read_to_string(&project_config_file)
+    {
+        Ok(text) => text.parse::<toml::Table>().map_err(NextestError::ProjectConfigParse)?,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => toml::Table::new(),
+        Err(error) => return Err(error.into())
+    };
+
+    let profiles = config
+        .entry("profile")
+        .or_insert_with(|| toml::Table::new().into())
+        .as_table_mut()
+        .ok_or(NextestError::ProjectConfigProfiles)?;
+
+    // A profile the project does not define is left out, so nextest still reports it as not found
+    if profile_name == NextestConfig::DEFAULT_PROFILE
+    {
+        profiles.entry(profile_name).or_insert_with(|| toml::Table::new().into());
+    }
+
+    if let Some(profile) = profiles.get_mut(profile_name)
+    {
+        let profile = profile.as_table_mut().ok_or(NextestError::ProjectConfigProfiles)?;
+
+        let mut slow_timeout = toml::Table::new();
+        slow_timeout.insert(String::from("period"), format!("{}ms", timeouts.period().as_millis()).into());
+
+        if let Some(terminate_after) = timeouts.terminate_after
+        {
+            slow_timeout.insert(String::from("terminate-after"), i64::from(terminate_after).into());
+        }
+
+        profile.insert(String::from("slow-timeout"), slow_timeout.into());
+    }
+
+    fs::create_dir_all(target_dir)?;
+    let config_file = target_dir.join("passivate-nextest.toml");
+    fs::write(&config_file, config.to_string())?;
+
+    Ok(Some(config_file))
+}
+
+fn nextest_hyp_id(test_instance: &TestInstanceId<'_>) -> HypId
+{
+    let mut binary_id = test_instance.binary_id.as_str().split("::");
+
+    let package_id = binary_id.next().unwrap();
+    let crate_id = binary_id.next().unwrap_or(package_id);
+
+    HypId::new(package_id, crate_id, test_instance.test_name.as_str())
+}
+
+/// A line of output a panic may be printed on
+fn panic_output_line(output: &RustOutput) -> Option<&str>
+{
+    match output
+    {
+        RustOutput::Console(ConsoleOutput {
+            content,
+            kind: ConsoleOutputKind::StdErr | ConsoleOutputKind::Combined
+        }) => Some(content),
+        _ => None
+    }
+}
+
+/// Test output is not necessarily UTF-8, so it is decoded lossily rather than dropped
+fn lossy(line: &[u8]) -> String
+{
+    String::from_utf8_lossy(line).into_owned()
+}
+
+fn process_nextest_event<TTx>(
+    tx: &mut TTx,
+    profile_recorder: &mut Option<HypProfileRecorder>,
+    progress: &mut RunProgress,
+    ignored: &mut HashSet<HypId>,
+    started: Instant,
+    summary: &mut RunSummary,
+    test_event: TestEvent<'_>
+) where
+    TTx: SendHypBridge<RustBridge>
+        + SendOutputBridge<RustBridge>
+        + SendProgressBridge<RustBridge>
+        + SendSummaryBridge<RustBridge>
 {
     match test_event.kind
     {
-        nextest_runner::reporter::events::TestEventKind::RunStarted {
-            test_list: _,
-            run_id: _,
-            profile_name: _,
-            cli_args: _,
-            stress_condition: _
-        } =>
-        {}
         nextest_runner::reporter::events::TestEventKind::TestStarted {
             stress_index: _,
-            test_instance: _,
+            test_instance,
             current_stats: _,
             running: _,
             command_line: _
         } =>
-        {}
+        {
+            let hyp_info = RustHyp::new_single(nextest_hyp_id(&test_instance));
+            tx.send_hyp(HypReport::new_fixed(hyp_info, HypState::Running));
+        }
         nextest_runner::reporter::events::TestEventKind::TestFinished {
             stress_index: _,
             test_instance,
@@ -335,76 +727,124 @@ where
             running: _
         } =>
         {
-            let test_output: Vec<String> = run_statuses
-                .iter()
-                .flat_map(|status| {
-                    match &status.output
+            let mut test_output = Vec::new();
+
+            for status in run_statuses.iter()
+            {
+                match &status.output
+                {
+                    ChildExecutionOutputDescription::Output { output, .. } =>
                     {
-                        ChildExecutionOutputDescription::Output { result, output, errors } =>
+                        match output
                         {
-                            match output
+                            ChildOutputDescription::Split { stdout, stderr } =>
                             {
-                                nextest_runner::reporter::events::ChildOutputDescription::Split { stdout, stderr } =>
-                                {
-                                    if let Some(stderr) = &stderr
-                                    {
-                                        stderr.lines().map(|l| String::from_utf8(l.to_vec()).unwrap()).collect()
-                                    }
-                                    else
-                                    {
-                                        Vec::new()
-                                    }
-                                }
-                                nextest_runner::reporter::events::ChildOutputDescription::Combined { output } => todo!()
+                                let stdout = stdout.iter().flat_map(|stdout| stdout.lines());
+                                let stderr = stderr.iter().flat_map(|stderr| stderr.lines());
+
+                                test_output
+                                    .extend(stdout.map(|line| RustOutput::Console(ConsoleOutput::new_stdout(lossy(line)))));
+                                test_output
+                                    .extend(stderr.map(|line| RustOutput::Console(ConsoleOutput::new_stderr(lossy(line)))));
+                            }
+                            ChildOutputDescription::Combined { output } =>
+                            {
+                                test_output.extend(
+                                    output
+                                        .lines()
+                                        .map(|line| RustOutput::Console(ConsoleOutput::new_combined(lossy(line))))
+                                );
                             }
                         }
-                        ChildExecutionOutputDescription::StartError(child_start_error_description) => todo!()
                     }
-                })
-                .collect();
-
-            let state = if let FinalStatusLevel::Pass = run_statuses.describe().final_status_level()
-            {
-                HypState::Passed
+                    ChildExecutionOutputDescription::StartError(start_error) =>
+                    {
+                        test_output.push(RustOutput::StartError(start_error.to_string()));
+                    }
+                }
             }
-            else
+
+            let state = match run_statuses.describe()
             {
-                HypState::Failed
+                ExecutionDescription::Success { .. } => HypState::Passed,
+                ExecutionDescription::Flaky { .. } => HypState::Flaky,
+                ExecutionDescription::Failure { .. }
+                    if matches!(run_statuses.last_status().result, ExecutionResultDescription::Timeout { .. }) =>
+                {
+                    HypState::TimedOut
+                }
+                ExecutionDescription::Failure { .. } => HypState::Failed
             };
 
-            let mut binary_id = test_instance.binary_id.as_str().split("::");
+            // A flaky hyp still failed before it passed, which is worth knowing the reason of
+            if matches!(state, HypState::Failed | HypState::Flaky)
+                && let Some(failure) = panic_failure::parse_panic(test_output.iter().filter_map(panic_output_line))
+            {
+                test_output.push(RustOutput::Failure(failure));
+            }
 
-            let package_id = binary_id.next().unwrap();
-            let crate_id = binary_id.next().unwrap_or(package_id);
+            let hyp_id = nextest_hyp_id(&test_instance);
 
-            let hyp_id = HypId::new(package_id, crate_id, test_instance.test_name.as_str());
+            if let Some(profile_recorder) = profile_recorder
+            {
+                let profile_directory =
+                    profile_runner::profile_directory(test_instance.binary_id.as_str(), test_instance.test_name.as_str());
+
+                profile_recorder.record(hyp_id.clone(), &profile_directory);
+            }
 
             let hyp_info = RustHyp::new_single(hyp_id.clone());
-            let hyp_report = HypReport::new_fixed(hyp_info, state);
+            let hyp_report = HypReport::new_fixed(hyp_info, state).with_duration(run_statuses.last_status().time_taken);
 
             tx.send_hyp(hyp_report);
 
-            for line in test_output
+            for output in test_output
+            {
+                tx.send_output(OutputReport::new(hyp_id.clone(), output));
+            }
+
+            progress.record(state);
+            progress.elapsed = started.elapsed();
+            tx.send_progress(*progress);
+        }
+        nextest_runner::reporter::events::TestEventKind::TestSkipped {
+            stress_index: _,
+            test_instance,
+            reason: MismatchReason::Ignored
+        } =>
+        {
+            let hyp_id = nextest_hyp_id(&test_instance);
+
+            if !ignored.insert(hyp_id.clone())
             {
-                let output_report = OutputReport::new(
-                    hyp_id.clone(),
-                    RustOutput::Console(ConsoleOutput {
-                        content: line,
-                        kind: ConsoleOutputKind::StdErr
-                    })
-                );
-
-                tx.send_output(output_report);
+                return;
             }
+
+            let hyp_info = RustHyp::new_single(hyp_id);
+            tx.send_hyp(HypReport::new_fixed(hyp_info, HypState::Skipped));
+
+            progress.record(HypState::Skipped);
+            progress.elapsed = started.elapsed();
+            tx.send_progress(*progress);
         }
         nextest_runner::reporter::events::TestEventKind::RunFinished {
             run_id: _,
             start_time: _,
-            elapsed: _,
-            run_stats: _,
+            elapsed,
+            run_stats,
             outstanding_not_seen: _
         } =>
-        {}
+        {
+            // Passivate does not stress run hyps
+            if let RunFinishedStats::Single(run_stats) = run_stats
+            {
+                summary.progress.total += run_stats.initial_run_count;
+                summary.progress.passed += run_stats.passed;
+                summary.progress.failed += run_stats.failed_count();
+            }
+
+            summary.elapsed += elapsed;
+        }
         _ =>
         {}
     };
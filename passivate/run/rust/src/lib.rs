@@ -6,5 +6,8 @@ pub mod hyp_run_handler;
 pub mod hyp_runner;
 pub mod model;
 pub mod nextest_error;
+pub mod profile_runner;
 
+mod cargo_build;
 mod nextest_cargo_options;
+mod panic_failure;
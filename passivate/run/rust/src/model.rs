@@ -7,6 +7,9 @@ use passivate_hyp_names::package_id::PackageId;
 use passivate_id_chain_tree::id_chain::IdChain;
 use passivate_model_bridge::bridge::Bridge;
 use passivate_model_bridge::bridge_hyp::BridgeHyp;
+use passivate_model_bridge::bridge_output::BridgeOutput;
+use passivate_model_bridge::compiler_diagnostic::CompilerDiagnostic;
+use passivate_model_bridge::hyp_failure::HypFailure;
 use passivate_model_bridge::hyp_session_event::{CompilationMessage, ConsoleOutput};
 use passivate_model_bridge::hyp_state::HypState;
 
@@ -98,7 +101,11 @@ pub enum RustOutput
 {
     Workspace(WorkspaceCompilation),
     Project(CompilationMessage),
-    Console(ConsoleOutput)
+    Diagnostic(CompilerDiagnostic),
+    Console(ConsoleOutput),
+    /// The hyp's process could not be started
+    StartError(String),
+    Failure(HypFailure)
 }
 
 impl Display for RustOutput
@@ -109,7 +116,31 @@ impl Display for RustOutput
         {
             RustOutput::Workspace(workspace_compilation) => workspace_compilation.fmt(f),
             RustOutput::Project(compilation_message) => compilation_message.fmt(f),
-            RustOutput::Console(console_output) => console_output.fmt(f)
+            RustOutput::Diagnostic(diagnostic) => diagnostic.fmt(f),
+            RustOutput::Console(console_output) => console_output.fmt(f),
+            RustOutput::StartError(error) => write!(f, "failed to start: {error}"),
+            RustOutput::Failure(failure) => failure.fmt(f)
+        }
+    }
+}
+
+impl BridgeOutput for RustOutput
+{
+    fn diagnostic(&self) -> Option<&CompilerDiagnostic>
+    {
+        match self
+        {
+            RustOutput::Diagnostic(diagnostic) => Some(diagnostic),
+            _ => None
+        }
+    }
+
+    fn failure(&self) -> Option<&HypFailure>
+    {
+        match self
+        {
+            RustOutput::Failure(failure) => Some(failure),
+            _ => None
         }
     }
 }
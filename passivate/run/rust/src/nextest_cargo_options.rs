@@ -1,6 +1,7 @@
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use cargo_nextest::cargo_cli::{CargoBuildScopeOptions, CargoOptions};
 use cargo_nextest::dispatch::CargoMessageFormatOpt;
+use passivate_configuration::run_scope::RunScope;
 
 #[bon::builder]
 pub fn cargo_build_scope_options(
@@ -44,6 +45,27 @@ pub fn cargo_build_scope_options(
     }
 }
 
+/// The cargo options of a run scope, which both nextest and `cargo_build` build with
+pub fn run_scope_options(run_scope: &RunScope, target_dir: &Utf8Path) -> CargoOptions
+{
+    let build_scope = cargo_build_scope_options()
+        .packages(run_scope.packages.clone())
+        .workspace(!run_scope.exclude.is_empty())
+        .exclude(run_scope.exclude.clone())
+        .all_targets(run_scope.all_targets)
+        .features(run_scope.features.clone())
+        .all_features(run_scope.all_features)
+        .no_default_features(run_scope.no_default_features)
+        .call();
+
+    cargo_options()
+        .build_scope(build_scope)
+        .release(run_scope.release)
+        .maybe_target(run_scope.target.clone())
+        .target_dir(target_dir.to_owned())
+        .call()
+}
+
 #[bon::builder]
 pub fn cargo_options(
     #[builder(default = cargo_build_scope_options().call())] build_scope: CargoBuildScopeOptions,
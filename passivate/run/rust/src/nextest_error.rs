@@ -1,7 +1,10 @@
 use std::convert::Infallible;
+use std::error::Error as StdError;
 use std::fmt::Display;
+use std::iter;
 
 use cargo_nextest::ExpectedError;
+use itertools::Itertools;
 use nextest_filtering::errors::ParseSingleError;
 use nextest_runner::errors::{
     CargoConfigError,
@@ -10,6 +13,8 @@ use nextest_runner::errors::{
     FromMessagesError,
     HostPlatformDetectError,
     ProfileNotFound,
+    TargetRunnerError,
+    TargetTripleError,
     TestFilterBuildError,
     TestRunnerBuildError,
     TestRunnerExecuteErrors
@@ -26,6 +31,10 @@ pub enum NextestError
     FiltersetParse(#[from] ParseSingleError),
     UnknownFiltersetParse,
     CargoConfig(#[from] CargoConfigError),
+    TargetRunner(#[from] TargetRunnerError),
+    TargetTriple(#[from] TargetTripleError),
+    ProjectConfigParse(#[from] toml::de::Error),
+    ProjectConfigProfiles,
     CreateTestList(#[from] CreateTestListError),
     TestFilterBuild(#[from] TestFilterBuildError),
     TestRunnerBuild(#[from] TestRunnerBuildError),
@@ -35,8 +44,44 @@ pub enum NextestError
 
 impl Display for NextestError
 {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
-        todo!()
+        match self
+        {
+            NextestError::HostPlatformDetect(error) => write!(f, "failed to detect the host platform: {error}"),
+            NextestError::ConfigParse(error) => write!(f, "failed to parse nextest configuration: {}", with_causes(error)),
+            NextestError::FromMessages(error) => write!(f, "failed to read test binaries from build output: {error}"),
+            NextestError::ProfileNotFound(error) => write!(f, "the configured nextest profile does not exist: {error}"),
+            NextestError::FiltersetParse(error) => write!(f, "failed to parse filterset: {error}"),
+            NextestError::UnknownFiltersetParse => write!(f, "failed to parse filterset"),
+            NextestError::CargoConfig(error) => write!(f, "failed to read cargo configuration: {error}"),
+            NextestError::TargetRunner(error) => write!(f, "failed to run tests through passivate to record coverage: {error}"),
+            NextestError::TargetTriple(error) => write!(f, "failed to find the target to build for: {}", with_causes(error)),
+            NextestError::ProjectConfigParse(error) =>
+            {
+                write!(
+                    f,
+                    "failed to read the project's nextest configuration to pass timeouts to it: {error}"
+                )
+            }
+            NextestError::ProjectConfigProfiles =>
+            {
+                write!(
+                    f,
+                    "failed to pass timeouts to nextest: the project's nextest profiles are not tables"
+                )
+            }
+            NextestError::CreateTestList(error) => write!(f, "failed to list tests: {error}"),
+            NextestError::TestFilterBuild(error) => write!(f, "failed to build test filter: {error}"),
+            NextestError::TestRunnerBuild(error) => write!(f, "failed to build test runner: {error}"),
+            NextestError::TestRunnerExecute(error) => write!(f, "failed to run tests: {error}"),
+            NextestError::Expected(error) => write!(f, "{error}")
+        }
     }
 }
+
+/// The error followed by what caused it, since nextest keeps the details of configuration errors in their sources
+fn with_causes(error: &(dyn StdError + 'static)) -> String
+{
+    iter::successors(Some(error), |&error| error.source()).join(": ")
+}
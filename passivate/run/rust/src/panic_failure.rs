@@ -0,0 +1,219 @@
+use camino::Utf8PathBuf;
+use passivate_model_bridge::hyp_failure::{AssertionValues, FailureLocation, HypFailure};
+
+const PANICKED_AT: &str = " panicked at ";
+
+/// Recognise the first panic in the stderr of a Rust test, with its location, message, assertion values and backtrace
+pub(crate) fn parse_panic<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<HypFailure>
+{
+    let mut lines = lines.into_iter().skip_while(|line| !is_panic(line));
+
+    let (_, panicked_at) = lines.next()?.split_once(PANICKED_AT)?;
+
+    let (location, mut message) = match panicked_at.strip_suffix(':')
+    {
+        // Since Rust 1.73 the message follows on the lines after the location
+        Some(location) => (parse_location(location), Vec::new()),
+        // Before that it was quoted on the same line, as in `panicked at 'message', src/lib.rs:6:5`
+        None =>
+        {
+            let (quoted_message, location) = panicked_at.rsplit_once(", ")?;
+            let quoted_message = quoted_message.trim_start_matches('\'').trim_end_matches('\'');
+
+            (parse_location(location), vec![quoted_message])
+        }
+    };
+
+    let mut left = None;
+    let mut right = None;
+    let mut backtrace = Vec::new();
+    let mut in_backtrace = false;
+
+    for line in lines
+    {
+        if line.starts_with("note:") || is_panic(line)
+        {
+            break;
+        }
+
+        if in_backtrace
+        {
+            backtrace.push(line.trim().to_string());
+        }
+        else if line == "stack backtrace:"
+        {
+            in_backtrace = true;
+        }
+        else if let Some(value) = line.trim_start().strip_prefix("left: ")
+        {
+            left = Some(value.to_string());
+        }
+        else if let Some(value) = line.trim_start().strip_prefix("right: ")
+        {
+            right = Some(value.to_string());
+        }
+        // Values debug printed with `{:#?}` span several lines, which continue the value before them
+        else if let Some(value) = right.as_mut().or(left.as_mut())
+        {
+            value.push('\n');
+            value.push_str(line);
+        }
+        else
+        {
+            message.push(line);
+        }
+    }
+
+    let assertion = match (left, right)
+    {
+        (Some(left), Some(right)) => Some(AssertionValues { left, right }),
+        _ => None
+    };
+
+    Some(HypFailure {
+        message: message.join("\n"),
+        location,
+        assertion,
+        backtrace
+    })
+}
+
+fn is_panic(line: &str) -> bool
+{
+    line.starts_with("thread '") && line.contains(PANICKED_AT)
+}
+
+/// A location as `file:line:column`, where the file may itself contain colons
+fn parse_location(location: &str) -> Option<FailureLocation>
+{
+    let mut parts = location.rsplitn(3, ':');
+
+    let column = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    let file = Utf8PathBuf::from(parts.next()?);
+
+    Some(FailureLocation { file, line, column })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::Utf8PathBuf;
+    use passivate_model_bridge::hyp_failure::{AssertionValues, FailureLocation};
+
+    use crate::panic_failure::parse_panic;
+
+    const ASSERT_EQ_FAILURE: &str = "\
+thread 'multiply_2_and_2_is_4' panicked at tests/multiply_tests.rs:6:5:
+assertion `left == right` failed
+  left: 5
+ right: 4
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace";
+
+    #[test]
+    pub fn output_without_a_panic_is_no_failure()
+    {
+        assert_eq!(None, parse_panic(["running 1 test", "test add_2_and_2_is_4 ... ok"]));
+    }
+
+    #[test]
+    pub fn panic_location_is_recognised()
+    {
+        let failure = parse_panic(ASSERT_EQ_FAILURE.lines()).unwrap();
+
+        assert_eq!(
+            Some(FailureLocation {
+                file: Utf8PathBuf::from("tests/multiply_tests.rs"),
+                line: 6,
+                column: 5
+            }),
+            failure.location
+        );
+    }
+
+    #[test]
+    pub fn assertion_message_and_values_are_recognised()
+    {
+        let failure = parse_panic(ASSERT_EQ_FAILURE.lines()).unwrap();
+
+        assert_eq!("assertion `left == right` failed", failure.message);
+        assert_eq!(
+            Some(AssertionValues {
+                left: String::from("5"),
+                right: String::from("4")
+            }),
+            failure.assertion
+        );
+    }
+
+    #[test]
+    pub fn assertion_values_spanning_lines_are_kept_whole()
+    {
+        let output = "\
+thread 'point_is_moved' panicked at tests/point_tests.rs:8:5:
+assertion `left == right` failed
+  left: Point {
+    x: 1,
+}
+ right: Point {
+    x: 2,
+}
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace";
+
+        let failure = parse_panic(output.lines()).unwrap();
+
+        assert_eq!("assertion `left == right` failed", failure.message);
+        assert_eq!(
+            Some(AssertionValues {
+                left: String::from("Point {\n    x: 1,\n}"),
+                right: String::from("Point {\n    x: 2,\n}")
+            }),
+            failure.assertion
+        );
+    }
+
+    #[test]
+    pub fn panic_message_spanning_lines_is_kept_whole()
+    {
+        let output = "thread 'main' panicked at src/lib.rs:10:9:\nsomething went wrong\nwhile adding";
+
+        let failure = parse_panic(output.lines()).unwrap();
+
+        assert_eq!("something went wrong\nwhile adding", failure.message);
+        assert_eq!(None, failure.assertion);
+    }
+
+    #[test]
+    pub fn panic_message_on_the_same_line_as_its_location_is_recognised()
+    {
+        let failure = parse_panic(["thread 'main' panicked at 'explicit panic', src/lib.rs:3:5"]).unwrap();
+
+        assert_eq!("explicit panic", failure.message);
+        assert_eq!(Some(3), failure.location.map(|location| location.line));
+    }
+
+    #[test]
+    pub fn backtrace_is_recognised()
+    {
+        let output = "\
+thread 'main' panicked at src/lib.rs:3:5:
+explicit panic
+stack backtrace:
+   0: rust_begin_unwind
+             at /rustc/library/std/src/panicking.rs:665:5
+   1: sample_project::multiply
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.";
+
+        let failure = parse_panic(output.lines()).unwrap();
+
+        assert_eq!("explicit panic", failure.message);
+        assert_eq!(
+            vec![
+                "0: rust_begin_unwind",
+                "at /rustc/library/std/src/panicking.rs:665:5",
+                "1: sample_project::multiply"
+            ],
+            failure.backtrace
+        );
+    }
+}
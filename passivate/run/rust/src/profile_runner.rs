@@ -0,0 +1,139 @@
+use std::env;
+use std::ffi::OsStr;
+use std::process::{self, Command};
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::hyp_run_error::HypRunError;
+
+/// Passed to passivate to run a test process rather than start the app, with the coverage directory and the test command
+///
+/// Nextest starts every test process through passivate itself when coverage is enabled, the same way it starts test
+/// processes through a target runner. That gives each test process a profile file of its own, which attributes the
+/// coverage to the hyp without running hyps one at a time.
+pub const PROFILE_RUNNER_ARG: &str = "__profile_runner";
+
+/// Cargo configuration making nextest start test processes through passivate, writing profiles to the coverage directory
+pub(crate) fn runner_config(coverage_dir: &Utf8Path) -> Result<String, HypRunError>
+{
+    let passivate = env::current_exe()?;
+    // Test processes run in the directory of their package, so the coverage directory has to be absolute
+    let coverage_dir = dunce::canonicalize(coverage_dir)?;
+
+    Ok(format!(
+        "target.'cfg(all())'.runner = [{:?}, {PROFILE_RUNNER_ARG:?}, {:?}]",
+        passivate.to_string_lossy(),
+        coverage_dir.to_string_lossy()
+    ))
+}
+
+/// The directory, relative to the coverage directory, a test process writes its profiles to
+pub fn profile_directory(binary_id: &str, test_name: &str) -> Utf8PathBuf
+{
+    Utf8PathBuf::from("hyps").join(escape(binary_id)).join(escape(test_name))
+}
+
+/// Runs the test command passivate was started with, if it was started as a test runner, and exits with its exit code
+pub fn run_if_requested()
+{
+    let mut args = env::args_os().skip(1);
+
+    if args.next().as_deref() != Some(OsStr::new(PROFILE_RUNNER_ARG))
+    {
+        return;
+    }
+
+    let (Some(coverage_dir), Some(program)) = (args.next(), args.next())
+    else
+    {
+        eprintln!("{PROFILE_RUNNER_ARG} expects a coverage directory and a test command");
+        process::exit(1);
+    };
+
+    let mut command = Command::new(program);
+    command.args(args);
+
+    // Nextest also runs test binaries to list their tests, whose profiles belong to no hyp
+    if let (Ok(binary_id), Ok(test_name)) = (env::var("NEXTEST_BINARY_ID"), env::var("NEXTEST_TEST_NAME"))
+    {
+        let profile_directory = Utf8Path::new(&coverage_dir.to_string_lossy()).join(profile_directory(&binary_id, &test_name));
+
+        command.env("LLVM_PROFILE_FILE", profile_directory.join("%p-%m.profraw"));
+    }
+
+    process::exit(run(command));
+}
+
+/// Replaces the runner with the test process, so nextest sees the test's own signals and exit code
+#[cfg(unix)]
+fn run(mut command: Command) -> i32
+{
+    use std::os::unix::process::CommandExt;
+
+    let error = command.exec();
+    eprintln!("failed to start {:?}: {error}", command.get_program());
+
+    1
+}
+
+#[cfg(not(unix))]
+fn run(mut command: Command) -> i32
+{
+    match command.status()
+    {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(error) =>
+        {
+            eprintln!("failed to start {:?}: {error}", command.get_program());
+            1
+        }
+    }
+}
+
+/// Binary ids and test names as a directory name, without the '%' that starts a pattern in LLVM_PROFILE_FILE
+fn escape(name: &str) -> String
+{
+    let mut escaped = String::with_capacity(name.len());
+
+    for byte in name.bytes()
+    {
+        if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-'
+        {
+            escaped.push(char::from(byte));
+        }
+        else
+        {
+            escaped.push_str(&format!("+{byte:02x}"));
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests
+{
+    use camino::Utf8PathBuf;
+
+    use crate::profile_runner::profile_directory;
+
+    #[test]
+    pub fn profiles_of_a_test_go_to_a_directory_of_its_binary_and_name()
+    {
+        let directory = profile_directory("sample_project::add_tests", "tests::add_2_and_2_is_4");
+
+        assert_eq!(
+            Utf8PathBuf::from("hyps/sample_project+3a+3aadd_tests/tests+3a+3aadd_2_and_2_is_4"),
+            directory
+        );
+    }
+
+    #[test]
+    pub fn a_test_name_that_looks_escaped_gets_a_directory_of_its_own()
+    {
+        assert_ne!(
+            profile_directory("sample_project", "a:"),
+            profile_directory("sample_project", "a+3a")
+        );
+    }
+}
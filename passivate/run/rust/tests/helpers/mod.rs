@@ -1,3 +1,8 @@
+use std::sync::Arc;
+
+use passivate_coverage::compute_coverage::{self, ComputeCoverage, MockComputeCoverage};
+use passivate_coverage::coverage_bridge::{self, MockCoverageBridge, SendCoverageBridge};
+use passivate_coverage::hyp_coverage::HypCoverage;
 use passivate_model_bridge::hyp_run_request::HypRunRequest;
 use passivate_model_bridge::hyp_session_bridge::MockHypSessionBridge;
 use passivate_run_rust::hyp_run_handler::{self, HypSessionBridge};
@@ -7,15 +12,18 @@ use tokio::runtime::Runtime;
 use tokio_util::sync::CancellationToken;
 
 /// Convenience builder to invoke 'hyp_run_handler::handle_request'
-pub struct HandleHypRunRequest<TRunHyps, THypSessionBridge>
+pub struct HandleHypRunRequest<TRunHyps, THypSessionBridge, TComputeCoverage, TCoverageBridge>
 {
     run_hyps: TRunHyps,
     hyp_session_bridge: THypSessionBridge,
+    compute_coverage: Arc<TComputeCoverage>,
+    coverage_bridge: TCoverageBridge,
+    hyp_coverage: HypCoverage,
     runtime: Option<Runtime>,
     cancellation: Option<CancellationToken>
 }
 
-impl HandleHypRunRequest<MockRunHyps, MockHypSessionBridge<RustBridge>>
+impl HandleHypRunRequest<MockRunHyps, MockHypSessionBridge<RustBridge>, MockComputeCoverage, MockCoverageBridge>
 {
     pub fn new() -> Self
     {
@@ -30,49 +38,119 @@ impl HandleHypRunRequest<MockRunHyps, MockHypSessionBridge<RustBridge>>
             .expect_run_hyp::<MockHypSessionBridge<RustBridge>>()
             .returning(|_, _, _| Ok(()));
 
+        mock_run_hyps
+            .expect_run_hyp_set::<MockHypSessionBridge<RustBridge>>()
+            .returning(|_, _, _| Ok(()));
+
+        mock_run_hyps
+            .expect_run_affected::<MockHypSessionBridge<RustBridge>>()
+            .returning(|_, _, _| Ok(()));
+
+        mock_run_hyps
+            .expect_run_failed_first::<MockHypSessionBridge<RustBridge>>()
+            .returning(|_, _, _| Ok(()));
+
         Self {
             run_hyps: mock_run_hyps,
             hyp_session_bridge: mock_hyp_session_bridge,
+            compute_coverage: Arc::new(compute_coverage::stub()),
+            coverage_bridge: coverage_bridge::stub(),
+            hyp_coverage: HypCoverage::default(),
             runtime: None,
             cancellation: None
         }
     }
 }
 
-impl<_TRunHyps, _THypSessionBridge> HandleHypRunRequest<_TRunHyps, _THypSessionBridge>
+impl<_TRunHyps, _THypSessionBridge, _TComputeCoverage, _TCoverageBridge>
+    HandleHypRunRequest<_TRunHyps, _THypSessionBridge, _TComputeCoverage, _TCoverageBridge>
 {
     pub fn with_hyp_session_bridge<THypSessionBridge>(
         self,
         hyp_session_bridge: THypSessionBridge
-    ) -> HandleHypRunRequest<_TRunHyps, THypSessionBridge>
+    ) -> HandleHypRunRequest<_TRunHyps, THypSessionBridge, _TComputeCoverage, _TCoverageBridge>
     where
         THypSessionBridge: HypSessionBridge<RustBridge>
     {
-        HandleHypRunRequest::<_TRunHyps, THypSessionBridge> {
+        HandleHypRunRequest {
             run_hyps: self.run_hyps,
             hyp_session_bridge,
+            compute_coverage: self.compute_coverage,
+            coverage_bridge: self.coverage_bridge,
+            hyp_coverage: self.hyp_coverage,
             runtime: self.runtime,
             cancellation: self.cancellation
         }
     }
 
-    pub fn with_runner<TRunHyps>(self, run_hyps: TRunHyps) -> HandleHypRunRequest<TRunHyps, _THypSessionBridge>
+    pub fn with_runner<TRunHyps>(
+        self,
+        run_hyps: TRunHyps
+    ) -> HandleHypRunRequest<TRunHyps, _THypSessionBridge, _TComputeCoverage, _TCoverageBridge>
     where
         TRunHyps: RunHyps
     {
-        HandleHypRunRequest::<TRunHyps, _THypSessionBridge> {
+        HandleHypRunRequest {
             run_hyps,
             hyp_session_bridge: self.hyp_session_bridge,
+            compute_coverage: self.compute_coverage,
+            coverage_bridge: self.coverage_bridge,
+            hyp_coverage: self.hyp_coverage,
+            runtime: self.runtime,
+            cancellation: self.cancellation
+        }
+    }
+
+    pub fn with_compute_coverage<TComputeCoverage>(
+        self,
+        compute_coverage: TComputeCoverage
+    ) -> HandleHypRunRequest<_TRunHyps, _THypSessionBridge, TComputeCoverage, _TCoverageBridge>
+    where
+        TComputeCoverage: ComputeCoverage
+    {
+        HandleHypRunRequest {
+            run_hyps: self.run_hyps,
+            hyp_session_bridge: self.hyp_session_bridge,
+            compute_coverage: Arc::new(compute_coverage),
+            coverage_bridge: self.coverage_bridge,
+            hyp_coverage: self.hyp_coverage,
+            runtime: self.runtime,
+            cancellation: self.cancellation
+        }
+    }
+
+    pub fn with_hyp_coverage(mut self, hyp_coverage: HypCoverage) -> Self
+    {
+        self.hyp_coverage = hyp_coverage;
+        self
+    }
+
+    pub fn with_coverage_bridge<TCoverageBridge>(
+        self,
+        coverage_bridge: TCoverageBridge
+    ) -> HandleHypRunRequest<_TRunHyps, _THypSessionBridge, _TComputeCoverage, TCoverageBridge>
+    where
+        TCoverageBridge: SendCoverageBridge
+    {
+        HandleHypRunRequest {
+            run_hyps: self.run_hyps,
+            hyp_session_bridge: self.hyp_session_bridge,
+            compute_coverage: self.compute_coverage,
+            coverage_bridge,
+            hyp_coverage: self.hyp_coverage,
             runtime: self.runtime,
             cancellation: self.cancellation
         }
     }
 }
 
-impl<TRunHyps, THypSessionBridge> HandleHypRunRequest<TRunHyps, THypSessionBridge>
+impl<TRunHyps, THypSessionBridge, TComputeCoverage, TCoverageBridge>
+    HandleHypRunRequest<TRunHyps, THypSessionBridge, TComputeCoverage, TCoverageBridge>
 where
     TRunHyps: RunHyps + Send + Sync + 'static,
-    THypSessionBridge: HypSessionBridge<RustBridge>
+    THypSessionBridge: HypSessionBridge<RustBridge>,
+    TComputeCoverage: ComputeCoverage + Send + Sync + 'static,
+    TCoverageBridge: SendCoverageBridge
 {
     pub fn call(&mut self, request: HypRunRequest<RustBridge>)
     {
@@ -80,7 +158,21 @@ where
         let cancellation = self.cancellation.get_or_insert(CancellationToken::new()).child_token();
 
         runtime.block_on(async {
-            hyp_run_handler::handle_request(&mut self.hyp_session_bridge, &mut self.run_hyps, request, cancellation).await;
+            hyp_run_handler::handle_request(
+                &mut self.hyp_session_bridge,
+                &mut self.run_hyps,
+                &self.compute_coverage,
+                &mut self.coverage_bridge,
+                &mut self.hyp_coverage,
+                request,
+                cancellation
+            )
+            .await;
         });
     }
+
+    pub fn hyp_coverage(&self) -> &HypCoverage
+    {
+        &self.hyp_coverage
+    }
 }
@@ -3,31 +3,51 @@ mod helpers;
 #[macro_use]
 extern crate assert_matches;
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Error as IoError;
+use std::process::Command;
 use std::sync::Arc;
 use std::time::Duration;
 
+use camino::{Utf8Path, Utf8PathBuf};
 use galvanic_assert::assert_that;
 use galvanic_assert::matchers::collection::contains_in_order;
 use itertools::Itertools;
 use mockall::Sequence;
 use passivate_configuration::configuration::PassivateConfiguration;
+use passivate_configuration::coverage_thresholds::CoverageThresholds;
 use passivate_configuration::default_paths;
+use passivate_configuration::hyp_timeouts::HypTimeouts;
+use passivate_configuration::ignored_hyps::IgnoredHyps;
+use passivate_configuration::run_scope::RunScope;
+use passivate_coverage::compute_coverage::{self, MockComputeCoverage};
+use passivate_coverage::coverage_bridge;
+use passivate_coverage::coverage_errors::CoverageError;
+use passivate_coverage::coverage_status::CoverageStatus;
+use passivate_coverage::grcov::covdir_json::CovdirJson;
+use passivate_coverage::hyp_coverage::HypCoverage;
+use passivate_coverage::lcov::{self, LcovRecord};
 use passivate_hyp_names::hyp_id::HypId;
 use passivate_hyp_names::hyp_name_strategy::HypNameStrategy;
 use passivate_hyp_names::test_name;
 use passivate_id_chain_tree::id_chain::IdChain;
+use passivate_model_bridge::bridge_hyp::BridgeHyp;
+use passivate_model_bridge::bridge_output::BridgeOutput;
+use passivate_model_bridge::compiler_diagnostic::DiagnosticLevel;
+use passivate_model_bridge::hyp_failure::{AssertionValues, FailureLocation};
+use passivate_model_bridge::hyp_report::HypReportState;
 use passivate_model_bridge::hyp_run_request::{self, HypRunRequest, HypRunRequestKind};
-use passivate_model_bridge::hyp_session_bridge::MockHypSessionBridge;
-use passivate_model_bridge::hyp_session_event::{ConsoleOutput, HypSessionEvent};
+use passivate_model_bridge::hyp_session_bridge::{self, MockHypSessionBridge};
+use passivate_model_bridge::hyp_session_event::{ConsoleOutput, ConsoleOutputKind, HypSessionEvent};
 use passivate_model_bridge::hyp_state::HypState;
 use passivate_model_core::hyp_session::HypSession;
 use passivate_run_rust::hyp_run_error::HypRunError;
 use passivate_run_rust::hyp_run_handler::{self, spawn_hyp_run_future};
 use passivate_run_rust::hyp_runner::{HypRunner, MockRunHyps};
-use passivate_run_rust::model::{RustBridge, RustOutput};
+use passivate_run_rust::model::{RustBridge, RustOutput, WorkspaceCompilation};
 use passivate_run_rust::nextest_error::NextestError;
+use passivate_testing::path_resolution::{copy_from_data_to_output, test_output_path};
 use passivate_testing::test_data_setup::TestDataSetup;
 use passivate_testing::test_snapshot_path::TestSnapshotPath;
 
@@ -59,7 +79,14 @@ pub fn hyp_run_thread_cancels_run_upon_new_request()
         .returning(|_, _: &mut MockHypSessionBridge<RustBridge>| Ok(()));
 
     let runtime = hyp_run_handler::build_tokio_runtime();
-    let handle = spawn_hyp_run_future(&runtime, hyp_run_trigger_rx, hyp_session_bridge, runner);
+    let handle = spawn_hyp_run_future(
+        &runtime,
+        hyp_run_trigger_rx,
+        hyp_session_bridge,
+        runner,
+        compute_coverage::stub(),
+        coverage_bridge::stub()
+    );
 
     hyp_run_trigger_tx
         .blocking_send(HypRunRequest::all(PassivateConfiguration::default(), default_paths::stub()))
@@ -100,6 +127,388 @@ pub fn running_single_hyp_leaves_session_in_passed_state()
     assert_matches!(session.state(), HypState::Passed);
 }
 
+#[test]
+pub fn ignored_hyps_are_reported_as_skipped()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_ignored_tests")
+        .build()
+        .clean_output();
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let ignored_hyp = HypId::new("sample_project", "add_tests", "add_2_and_4_is_6");
+    let other_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+
+    assert_matches!(session.hyps().get(ignored_hyp.chain()).unwrap().state(), HypState::Skipped);
+    assert_matches!(session.hyps().get(other_hyp.chain()).unwrap().state(), HypState::Passed);
+}
+
+#[test]
+pub fn only_ignored_hyps_run_when_configured()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_ignored_tests")
+        .build()
+        .clean_output();
+
+    let configuration = PassivateConfiguration {
+        ignored_hyps: IgnoredHyps::Only,
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let ignored_hyp = HypId::new("sample_project", "add_tests", "add_2_and_4_is_6");
+    let other_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+
+    assert_matches!(session.hyps().get(ignored_hyp.chain()).unwrap().state(), HypState::Passed);
+    assert_matches!(session.hyps().get(other_hyp.chain()).unwrap().state(), HypState::Skipped);
+}
+
+#[test]
+pub fn single_ignored_hyp_runs_on_demand()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_ignored_tests")
+        .build()
+        .clean_output();
+
+    let hyp_to_run = HypId::new("sample_project", "add_tests", "add_2_and_4_is_6");
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::Single {
+                    hyp_id: hyp_to_run.clone()
+                })
+                .paths(setup.paths())
+                .call()
+        );
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    assert_matches!(session.hyps().get(hyp_to_run.chain()).unwrap().state(), HypState::Passed);
+}
+
+#[test]
+pub fn affected_hyp_run_only_runs_hyps_of_packages_affected_by_changed_files()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_workspace")
+        .build()
+        .clean_output();
+    let changed_file = setup.workspace_path().join("project_b").join("src").join("lib.rs");
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::Affected {
+                    changed_files: vec![changed_file]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let affected_hyp = HypId::new("project_b", "add_tests", "add_2_and_2_is_4_b");
+    let unaffected_hyp = HypId::new("project_a", "add_tests", "add_2_and_2_is_4_a");
+
+    assert!(session.hyps().get(affected_hyp.chain()).is_some());
+    assert!(session.hyps().get(unaffected_hyp.chain()).is_none());
+}
+
+#[test]
+pub fn failed_first_hyp_run_reports_failed_hyps_before_the_rest()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_failing_tests")
+        .build()
+        .clean_output();
+    let failed_hyp = HypId::new("sample_project", "multiply_tests", "multiply_2_and_2_is_4");
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::FailedFirst {
+                    failed_hyps: vec![failed_hyp.clone()]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+
+    let reported_hyps = session_rx
+        .try_iter()
+        .filter_map(|event| {
+            match event
+            {
+                HypSessionEvent::Hyp(hyp_report)
+                    if matches!(hyp_report.state, HypReportState::Fixed(HypState::Passed | HypState::Failed)) =>
+                {
+                    Some(hyp_report.hyp_info.id().clone())
+                }
+                _ => None
+            }
+        })
+        .collect_vec();
+
+    assert_eq!(Some(&failed_hyp), reported_hyps.first());
+    assert_eq!(3, reported_hyps.len());
+}
+
+#[test]
+pub fn failed_first_hyp_run_is_summarized_as_one_run()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_failing_tests")
+        .build()
+        .clean_output();
+    let failed_hyp = HypId::new("sample_project", "multiply_tests", "multiply_2_and_2_is_4");
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::FailedFirst {
+                    failed_hyps: vec![failed_hyp]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+
+    let events = session_rx.try_iter().collect_vec();
+
+    let totals = events
+        .iter()
+        .filter_map(|event| {
+            match event
+            {
+                HypSessionEvent::Progress(progress) => Some(progress.total),
+                _ => None
+            }
+        })
+        .unique()
+        .collect_vec();
+
+    let summaries = events
+        .iter()
+        .filter_map(|event| {
+            match event
+            {
+                HypSessionEvent::Summary(summary) => Some(summary.progress.finished()),
+                _ => None
+            }
+        })
+        .collect_vec();
+
+    assert_eq!(vec![3], totals);
+    assert_eq!(vec![3], summaries);
+}
+
+#[test]
+pub fn failed_first_hyp_run_counts_ignored_hyps_once()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_ignored_tests")
+        .build()
+        .clean_output();
+    let failed_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::FailedFirst {
+                    failed_hyps: vec![failed_hyp]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    assert_eq!(Some(1), session.summary().map(|summary| summary.progress.skipped));
+}
+
+#[test]
+pub fn hyps_are_reported_as_running_before_they_finish()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build().clean_output();
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let reported_states = session_rx
+        .try_iter()
+        .filter_map(|event| {
+            match event
+            {
+                HypSessionEvent::Hyp(hyp_report) => Some((hyp_report.hyp_info.id().clone(), hyp_report.state)),
+                _ => None
+            }
+        })
+        .collect_vec();
+
+    let add_2_and_2 = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+    let states = reported_states
+        .into_iter()
+        .filter_map(|(id, state)| (id == add_2_and_2).then_some(state))
+        .collect_vec();
+
+    assert_eq!(
+        vec![
+            HypReportState::Derived,
+            HypReportState::Fixed(HypState::Running),
+            HypReportState::Fixed(HypState::Passed)
+        ],
+        states
+    );
+}
+
+#[test]
+pub fn hyps_and_the_run_report_how_long_they_took()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build().clean_output();
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let add_2_and_2 = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+
+    assert!(session.hyps().get(add_2_and_2.chain()).unwrap().duration().is_some());
+
+    let summary = session.summary().expect("the run completed with a summary");
+
+    assert!(summary.elapsed > Duration::ZERO);
+    assert_eq!(0, summary.progress.failed);
+    assert_eq!(0, summary.progress.remaining());
+}
+
+#[test]
+pub fn build_progress_is_reported_to_the_session_while_hyps_build()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build().clean_output();
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let build_progress = session
+        .iter_output()
+        .filter_map(|output| {
+            match output
+            {
+                RustOutput::Workspace(WorkspaceCompilation::Message(message)) => Some(message.content.as_str()),
+                _ => None
+            }
+        })
+        .collect_vec();
+
+    assert!(
+        build_progress
+            .iter()
+            .any(|progress| progress.starts_with("Compiling sample_project"))
+    );
+    assert!(build_progress.iter().any(|progress| progress.starts_with("Finished")));
+}
+
+#[test]
+pub fn build_errors_are_reported_as_compiler_diagnostics()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_build_error")
+        .build()
+        .clean_output();
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let error = session
+        .iter_output()
+        .filter_map(BridgeOutput::diagnostic)
+        .find(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+        .unwrap();
+
+    assert_eq!(Some("E0308"), error.code.as_deref());
+    assert_matches!(error.primary_span(), Some(span) =>
+    {
+        assert_eq!("src/lib.rs", span.file);
+        assert_eq!(6, span.line_start);
+    });
+    assert!(
+        error
+            .rendered
+            .as_ref()
+            .is_some_and(|rendered| rendered.contains("mismatched types"))
+    );
+}
+
+#[test]
+pub fn build_failure_is_distinguished_from_failing_hyps()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_build_error")
+        .build()
+        .clean_output();
+
+    HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    assert_matches!(session.activity(), Ok(HypState::BuildFailed));
+    assert_matches!(session.build_errors(), [error] => assert_eq!(Some("E0308"), error.code.as_deref()));
+}
+
 #[test]
 pub fn single_hyp_run_only_runs_one_exact_hyp()
 {
@@ -226,7 +635,7 @@ pub fn failing_tests_output_is_captured_in_state() -> Result<(), IoError>
     ];
     assert_that!(
         // Skip first 2 lines to avoid a thread ID that is not deterministic
-        &failed_test.iter_output().skip(2).collect::<Vec<_>>(),
+        &stderr_output(failed_test.iter_output()).skip(2).collect::<Vec<_>>(),
         contains_in_order(expected.iter())
     );
 
@@ -234,53 +643,363 @@ pub fn failing_tests_output_is_captured_in_state() -> Result<(), IoError>
 }
 
 #[test]
-pub fn failing_tests_output_persists_on_repeat_runs() -> Result<(), IoError>
+pub fn stdout_of_hyps_is_reported()
 {
     let (session_tx, session_rx) = crossbeam_channel::unbounded();
 
-    let setup = TestDataSetup::builder(test_name!(), "simple_project_failing_tests")
-        .build()
-        .clean_output();
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build().clean_output();
 
-    let mut handle_hyp_run = HandleHypRunRequest::new()
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
         .with_runner(HypRunner)
-        .with_hyp_session_bridge(session_tx);
-
-    // Run tests twice
-    handle_hyp_run.call(HypRunRequest::stub().paths(setup.paths()).call());
-    handle_hyp_run.call(HypRunRequest::stub().paths(setup.paths()).call());
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
 
-    let failed_hyp = HypId::new("sample_project", "multiply_tests", "multiply_2_and_2_is_4");
+    let passed_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
 
     let session = HypSession::from_events(session_rx.try_iter());
 
-    let failed_test = session.hyps().get(&failed_hyp).unwrap();
-
-    let expected = [
-        RustOutput::Console(ConsoleOutput::new_stderr("assertion `left == right` failed")),
-        RustOutput::Console(ConsoleOutput::new_stderr("  left: 5")),
-        RustOutput::Console(ConsoleOutput::new_stderr(" right: 4")),
-        RustOutput::Console(ConsoleOutput::new_stderr(
-            "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace"
-        ))
-    ];
+    let passed_hyp = session.hyps().get(passed_hyp.chain()).unwrap();
 
-    assert_that!(
-        // Skip first 2 lines to avoid a thread ID that is not deterministic
-        &failed_test.iter_output().skip(2).collect::<Vec<_>>(),
-        contains_in_order(expected.iter())
+    assert!(
+        passed_hyp
+            .iter_output()
+            .any(|output| *output == RustOutput::Console(ConsoleOutput::new_stdout("running 1 test")))
     );
-
-    Ok(())
 }
 
 #[test]
-pub fn when_hyp_run_fails_error_is_reported()
+pub fn failed_assertion_is_reported_as_a_structured_failure()
 {
-    let mut run_hyps = MockRunHyps::new();
-    run_hyps
-        .expect_run_hyps::<crossbeam_channel::Sender<HypSessionEvent<RustBridge>>>()
-        .returning(|_, _| Err(HypRunError::Nextest(Arc::new(NextestError::UnknownFiltersetParse))));
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_failing_tests")
+        .build()
+        .clean_output();
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let failed_hyp = HypId::new("sample_project", "multiply_tests", "multiply_2_and_2_is_4");
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let failure = session
+        .hyps()
+        .get(failed_hyp.chain())
+        .unwrap()
+        .iter_output()
+        .find_map(BridgeOutput::failure)
+        .unwrap();
+
+    assert_eq!("assertion `left == right` failed", failure.message);
+    assert_eq!(
+        Some(FailureLocation {
+            file: Utf8PathBuf::from("tests/multiply_tests.rs"),
+            line: 6,
+            column: 5
+        }),
+        failure.location
+    );
+    assert_eq!(
+        Some(AssertionValues {
+            left: String::from("5"),
+            right: String::from("4")
+        }),
+        failure.assertion
+    );
+}
+
+#[test]
+pub fn hyp_that_passes_when_retried_is_flaky()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_flaky_tests")
+        .build()
+        .clean_output();
+
+    let configuration = PassivateConfiguration {
+        retries: Some(1),
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let flaky_hyp = session
+        .hyps()
+        .get(HypId::new("sample_project", "add_tests", "add_2_and_4_is_6_every_other_time").chain())
+        .unwrap();
+    let stable_hyp = session
+        .hyps()
+        .get(HypId::new("sample_project", "add_tests", "add_2_and_2_is_4").chain())
+        .unwrap();
+
+    assert_matches!(flaky_hyp.state(), HypState::Flaky);
+    assert_eq!(1, flaky_hyp.flaky_runs());
+    assert_matches!(stable_hyp.state(), HypState::Passed);
+}
+
+#[test]
+pub fn hyp_running_too_long_is_terminated_with_its_output_so_far()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_hanging_tests")
+        .build()
+        .clean_output();
+
+    let configuration = PassivateConfiguration {
+        timeouts: HypTimeouts {
+            period_ms: Some(500),
+            terminate_after: Some(2)
+        },
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let hanging_hyp = session
+        .hyps()
+        .get(HypId::new("sample_project", "add_tests", "add_2_and_4_is_6_eventually").chain())
+        .unwrap();
+
+    assert_matches!(hanging_hyp.state(), HypState::TimedOut);
+    assert!(hanging_hyp.iter_output().any(|output| {
+        matches!(output, RustOutput::Console(console) if console.content.ends_with("waiting before adding"))
+    }));
+}
+
+#[test]
+pub fn configured_timeouts_take_precedence_over_those_of_the_nextest_profile_of_the_project()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_hanging_tests")
+        .build()
+        .clean_output();
+
+    let configuration = PassivateConfiguration {
+        timeouts: HypTimeouts {
+            period_ms: Some(500),
+            terminate_after: Some(2)
+        },
+        nextest_profile: Some(String::from("passivate")),
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    // The rest of the profile still applies, its default filter only selects the hanging hyp
+    let hanging_hyp = HypId::new("sample_project", "add_tests", "add_2_and_4_is_6_eventually");
+    let add_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+
+    assert_matches!(session.hyps().get(hanging_hyp.chain()).unwrap().state(), HypState::TimedOut);
+    assert!(session.hyps().get(add_hyp.chain()).is_none());
+}
+
+#[test]
+pub fn hyps_run_with_the_configured_nextest_profile_of_the_project()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_nextest_profile")
+        .build()
+        .clean_output();
+
+    let configuration = PassivateConfiguration {
+        nextest_profile: Some(String::from("passivate")),
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    // The profile's default filter only selects the multiply tests
+    let multiply_hyp = HypId::new("sample_project", "multiply_tests", "multiply_2_and_2_is_4");
+    let add_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+
+    assert_matches!(session.hyps().get(multiply_hyp.chain()).unwrap().state(), HypState::Passed);
+    assert!(session.hyps().get(add_hyp.chain()).is_none());
+}
+
+#[test]
+pub fn feature_gated_hyps_run_when_their_feature_is_in_the_run_scope()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_features")
+        .build()
+        .clean_output();
+
+    let configuration = PassivateConfiguration {
+        run_scope: RunScope {
+            features: vec![String::from("subtract")],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let subtract_hyp = HypId::new("sample_project", "subtract_tests", "subtract_2_from_6_is_4");
+
+    assert_matches!(session.hyps().get(subtract_hyp.chain()).unwrap().state(), HypState::Passed);
+}
+
+#[test]
+pub fn feature_gated_hyps_are_left_out_without_their_feature()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_features")
+        .build()
+        .clean_output();
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let add_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+    let subtract_hyp = HypId::new("sample_project", "subtract_tests", "subtract_2_from_6_is_4");
+
+    assert_matches!(session.hyps().get(add_hyp.chain()).unwrap().state(), HypState::Passed);
+    assert!(session.hyps().get(subtract_hyp.chain()).is_none());
+}
+
+#[test]
+pub fn hyps_run_when_built_for_a_configured_target()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build().clean_output();
+
+    // The host is the one target the hyps are sure to run on
+    let host = Command::new("rustc").args(["--print", "host-tuple"]).output().unwrap();
+
+    let configuration = PassivateConfiguration {
+        run_scope: RunScope {
+            target: Some(String::from_utf8(host.stdout).unwrap().trim().to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let add_hyp = HypId::new("sample_project", "add_tests", "add_2_and_2_is_4");
+
+    assert_matches!(session.hyps().get(add_hyp.chain()).unwrap().state(), HypState::Passed);
+}
+
+#[test]
+pub fn unknown_nextest_profile_is_reported_as_run_error()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_nextest_profile")
+        .build()
+        .clean_output();
+
+    let configuration = PassivateConfiguration {
+        nextest_profile: Some(String::from("nonexistent")),
+        ..Default::default()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .call(HypRunRequest::stub().paths(setup.paths()).configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let failure_reason = session.failure_reason().unwrap();
+
+    assert!(failure_reason.contains("the configured nextest profile does not exist"));
+    assert!(failure_reason.contains("nonexistent"));
+}
+
+#[test]
+pub fn failing_tests_output_persists_on_repeat_runs() -> Result<(), IoError>
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project_failing_tests")
+        .build()
+        .clean_output();
+
+    let mut handle_hyp_run = HandleHypRunRequest::new()
+        .with_runner(HypRunner)
+        .with_hyp_session_bridge(session_tx);
+
+    // Run tests twice
+    handle_hyp_run.call(HypRunRequest::stub().paths(setup.paths()).call());
+    handle_hyp_run.call(HypRunRequest::stub().paths(setup.paths()).call());
+
+    let failed_hyp = HypId::new("sample_project", "multiply_tests", "multiply_2_and_2_is_4");
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    let failed_test = session.hyps().get(&failed_hyp).unwrap();
+
+    let expected = [
+        RustOutput::Console(ConsoleOutput::new_stderr("assertion `left == right` failed")),
+        RustOutput::Console(ConsoleOutput::new_stderr("  left: 5")),
+        RustOutput::Console(ConsoleOutput::new_stderr(" right: 4")),
+        RustOutput::Console(ConsoleOutput::new_stderr(
+            "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace"
+        ))
+    ];
+
+    assert_that!(
+        // Skip first 2 lines to avoid a thread ID that is not deterministic
+        &stderr_output(failed_test.iter_output()).skip(2).collect::<Vec<_>>(),
+        contains_in_order(expected.iter())
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn when_hyp_run_fails_error_is_reported()
+{
+    let mut run_hyps = MockRunHyps::new();
+    run_hyps
+        .expect_run_hyps::<crossbeam_channel::Sender<HypSessionEvent<RustBridge>>>()
+        .returning(|_, _| Err(HypRunError::Nextest(Arc::new(NextestError::UnknownFiltersetParse))));
 
     let (session_tx, session_rx) = crossbeam_channel::unbounded();
 
@@ -293,3 +1012,631 @@ pub fn when_hyp_run_fails_error_is_reported()
 
     assert_matches!(session.activity(), Ok(HypState::Failed));
 }
+
+#[test]
+pub fn when_coverage_is_enabled_hyps_run_with_coverage_output()
+{
+    let mut run_hyps = MockRunHyps::new();
+    run_hyps
+        .expect_run_hyps::<MockHypSessionBridge<RustBridge>>()
+        .withf(|options, _| options.coverage_dir.is_some())
+        .once()
+        .returning(|_, _| Ok(()));
+
+    HandleHypRunRequest::new()
+        .with_runner(run_hyps)
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .call(HypRunRequest::stub().configuration(coverage_enabled()).call());
+}
+
+#[test]
+pub fn when_coverage_is_enabled_coverage_is_computed_after_hyps_run()
+{
+    let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().once().returning(|_| Ok(()));
+    compute_coverage
+        .expect_compute_coverage()
+        .once()
+        .returning(|_, _| Ok(CoverageStatus::Done(Box::default())));
+    compute_coverage
+        .expect_compute_hyp_coverage()
+        .returning(|_, _| Ok(HypCoverage::default()));
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_compute_coverage(compute_coverage)
+        .with_coverage_bridge(coverage_tx)
+        .call(HypRunRequest::stub().configuration(coverage_enabled()).call());
+
+    let expected = vec![
+        CoverageStatus::Preparing,
+        CoverageStatus::Running,
+        CoverageStatus::Done(Box::default()),
+    ];
+
+    assert_eq!(expected, coverage_rx.try_iter().collect_vec());
+}
+
+#[test]
+pub fn coverage_maps_the_binaries_of_the_profile_and_target_the_hyps_are_built_for()
+{
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().returning(|_| Ok(()));
+    compute_coverage
+        .expect_compute_coverage()
+        .withf(|options, _| {
+            options
+                .binary_path
+                .ends_with(Utf8Path::new("coverage_target/x86_64-unknown-linux-gnu/release"))
+        })
+        .once()
+        .returning(|_, _| Ok(CoverageStatus::Done(Box::default())));
+    compute_coverage
+        .expect_compute_hyp_coverage()
+        .returning(|_, _| Ok(HypCoverage::default()));
+
+    let configuration = PassivateConfiguration {
+        run_scope: RunScope {
+            release: true,
+            target: Some(String::from("x86_64-unknown-linux-gnu")),
+            ..Default::default()
+        },
+        ..coverage_enabled()
+    };
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_compute_coverage(compute_coverage)
+        .call(HypRunRequest::stub().configuration(configuration).call());
+}
+
+#[test]
+pub fn when_coverage_fails_to_compute_error_is_reported()
+{
+    let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().returning(|_| Ok(()));
+    compute_coverage
+        .expect_compute_coverage()
+        .returning(|_, _| Err(CoverageError::GrcovNotInstalled(std::io::ErrorKind::NotFound)));
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_compute_coverage(compute_coverage)
+        .with_coverage_bridge(coverage_tx)
+        .call(HypRunRequest::stub().configuration(coverage_enabled()).call());
+
+    assert_matches!(coverage_rx.try_iter().last(), Some(CoverageStatus::Error(_)));
+}
+
+#[test]
+pub fn when_coverage_is_computed_coverage_of_each_hyp_is_kept()
+{
+    let hyp_id = HypId::new("example_package", "example_crate", "example_hyp");
+    let hyp_coverage = {
+        let mut hyp_coverage = HypCoverage::default();
+        hyp_coverage.insert(hyp_id.clone(), CovdirJson::default());
+        hyp_coverage
+    };
+
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().returning(|_| Ok(()));
+    compute_coverage
+        .expect_compute_coverage()
+        .returning(|_, _| Ok(CoverageStatus::Done(Box::default())));
+    compute_coverage
+        .expect_compute_hyp_coverage()
+        .once()
+        .returning(move |_, _| Ok(hyp_coverage.clone()));
+
+    let mut handle_hyp_run = HandleHypRunRequest::new()
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_compute_coverage(compute_coverage);
+
+    handle_hyp_run.call(HypRunRequest::stub().configuration(coverage_enabled()).call());
+
+    assert!(handle_hyp_run.hyp_coverage().covered_by(&hyp_id).is_some());
+}
+
+#[test]
+pub fn when_coverage_fails_to_compute_coverage_of_each_hyp_is_not_computed()
+{
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().returning(|_| Ok(()));
+    compute_coverage
+        .expect_compute_coverage()
+        .returning(|_, _| Err(CoverageError::GrcovNotInstalled(std::io::ErrorKind::NotFound)));
+    compute_coverage.expect_compute_hyp_coverage().never();
+
+    let mut handle_hyp_run = HandleHypRunRequest::new()
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_compute_coverage(compute_coverage);
+
+    handle_hyp_run.call(HypRunRequest::stub().configuration(coverage_enabled()).call());
+
+    assert!(handle_hyp_run.hyp_coverage().is_empty());
+}
+
+#[test]
+pub fn when_coverage_is_below_threshold_run_fails_with_reason()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    HandleHypRunRequest::new()
+        .with_runner(run_hyps_passing())
+        .with_hyp_session_bridge(session_tx)
+        .with_compute_coverage(compute_coverage_percent(50.0))
+        .call(HypRunRequest::stub().configuration(coverage_minimum(80.0)).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    assert_matches!(session.activity(), Ok(HypState::Failed));
+    assert_eq!(
+        Some("total coverage is 50%, below the minimum of 80%"),
+        session.failure_reason()
+    );
+}
+
+#[test]
+pub fn when_coverage_thresholds_cannot_be_checked_run_fails_with_reason()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let configuration = PassivateConfiguration {
+        coverage_thresholds: CoverageThresholds {
+            files: BTreeMap::from([(String::from("src/["), 80.0)]),
+            ..Default::default()
+        },
+        ..coverage_enabled()
+    };
+
+    HandleHypRunRequest::new()
+        .with_runner(run_hyps_passing())
+        .with_hyp_session_bridge(session_tx)
+        .with_compute_coverage(compute_coverage_percent(50.0))
+        .call(HypRunRequest::stub().configuration(configuration).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    assert_matches!(session.activity(), Ok(HypState::Failed));
+    assert_matches!(
+        session.failure_reason(),
+        Some(reason) if reason.starts_with("coverage thresholds could not be checked: invalid coverage threshold glob")
+    );
+}
+
+#[test]
+pub fn when_coverage_meets_threshold_run_completes()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    HandleHypRunRequest::new()
+        .with_runner(run_hyps_passing())
+        .with_hyp_session_bridge(session_tx)
+        .with_compute_coverage(compute_coverage_percent(80.0))
+        .call(HypRunRequest::stub().configuration(coverage_minimum(80.0)).call());
+
+    let session = HypSession::from_events(session_rx.try_iter());
+
+    assert_matches!(session.activity(), Ok(HypState::Passed));
+}
+
+#[test]
+pub fn when_new_request_arrives_while_coverage_computes_coverage_is_cancelled()
+{
+    let (hyp_run_trigger_tx, hyp_run_trigger_rx) = tokio::sync::mpsc::channel(1);
+    let (session_tx, _session_rx) = crossbeam_channel::unbounded();
+    let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().returning(|_| Ok(()));
+    compute_coverage.expect_compute_coverage().returning(|_, cancellation| {
+        while !cancellation.is_cancelled()
+        {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        Err(CoverageError::Cancelled)
+    });
+    compute_coverage.expect_compute_hyp_coverage().never();
+
+    let runtime = hyp_run_handler::build_tokio_runtime();
+    let handle = spawn_hyp_run_future(
+        &runtime,
+        hyp_run_trigger_rx,
+        session_tx,
+        run_hyps_passing(),
+        compute_coverage,
+        coverage_tx
+    );
+
+    let mut statuses = Vec::new();
+
+    for _ in 0 .. 2
+    {
+        hyp_run_trigger_tx
+            .blocking_send(HypRunRequest::all(coverage_enabled(), default_paths::stub()))
+            .unwrap();
+
+        // Wait for coverage to be computing before the next request cancels it
+        loop
+        {
+            let status = coverage_rx.recv_timeout(Duration::from_secs(6)).unwrap();
+            statuses.push(status.clone());
+
+            if status == CoverageStatus::Running
+            {
+                break;
+            }
+        }
+    }
+
+    drop(hyp_run_trigger_tx);
+
+    runtime.block_on(async {
+        _ = tokio::time::timeout(Duration::from_secs(6), handle).await;
+    });
+
+    statuses.extend(coverage_rx.try_iter());
+
+    let expected = vec![
+        CoverageStatus::Preparing,
+        CoverageStatus::Running,
+        CoverageStatus::Cancelled,
+        CoverageStatus::Preparing,
+        CoverageStatus::Running,
+        CoverageStatus::Cancelled,
+    ];
+
+    assert_eq!(expected, statuses);
+}
+
+#[test]
+pub fn run_whose_coverage_is_cancelled_is_cancelled()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().returning(|_| Ok(()));
+    compute_coverage
+        .expect_compute_coverage()
+        .returning(|_, _| Err(CoverageError::Cancelled));
+    compute_coverage.expect_compute_hyp_coverage().never();
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(run_hyps_passing())
+        .with_compute_coverage(compute_coverage)
+        .call(HypRunRequest::stub().configuration(coverage_enabled()).call());
+
+    let events: Vec<_> = session_rx.try_iter().collect();
+
+    assert_matches!(events.last(), Some(HypSessionEvent::RunCancelled));
+    assert!(!events.iter().any(|event| matches!(event, HypSessionEvent::RunCompleted)));
+}
+
+#[test]
+pub fn when_coverage_is_disabled_coverage_is_not_computed()
+{
+    let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().never();
+    compute_coverage.expect_compute_coverage().never();
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_compute_coverage(compute_coverage)
+        .with_coverage_bridge(coverage_tx)
+        .call(HypRunRequest::stub().call());
+
+    assert_eq!(vec![CoverageStatus::Disabled], coverage_rx.try_iter().collect_vec());
+}
+
+#[test]
+pub fn when_changed_lines_are_covered_only_hyps_covering_them_run()
+{
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build();
+
+    let mut run_hyps = MockRunHyps::new();
+    run_hyps
+        .expect_run_hyp_set::<MockHypSessionBridge<RustBridge>>()
+        .once()
+        .withf(|hyp_ids, _, _| hyp_ids == &[add_hyp()])
+        .returning(|_, _, _| Ok(()));
+    run_hyps.expect_run_hyps::<MockHypSessionBridge<RustBridge>>().never();
+
+    HandleHypRunRequest::new()
+        .with_runner(run_hyps)
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_hyp_coverage(simple_project_hyp_coverage(&setup))
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::Impacted {
+                    changed_files: vec![setup.workspace_path().join("src").join("lib.rs")]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+}
+
+#[test]
+pub fn when_no_coverage_was_recorded_impacted_run_runs_every_hyp()
+{
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build();
+
+    let mut run_hyps = MockRunHyps::new();
+    run_hyps
+        .expect_run_hyps::<MockHypSessionBridge<RustBridge>>()
+        .once()
+        .returning(|_, _| Ok(()));
+    run_hyps.expect_run_hyp_set::<MockHypSessionBridge<RustBridge>>().never();
+
+    HandleHypRunRequest::new()
+        .with_runner(run_hyps)
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::Impacted {
+                    changed_files: vec![setup.workspace_path().join("src").join("lib.rs")]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+}
+
+#[test]
+pub fn when_impacted_hyps_run_coverage_is_kept_as_it_was()
+{
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build();
+    let (coverage_tx, coverage_rx) = crossbeam_channel::unbounded();
+
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().never();
+    compute_coverage.expect_compute_coverage().never();
+
+    let mut handle_hyp_run = HandleHypRunRequest::new()
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_compute_coverage(compute_coverage)
+        .with_coverage_bridge(coverage_tx)
+        .with_hyp_coverage(simple_project_hyp_coverage(&setup));
+
+    handle_hyp_run.call(
+        HypRunRequest::stub()
+            .kind(HypRunRequestKind::Impacted {
+                changed_files: vec![setup.workspace_path().join("src").join("lib.rs")]
+            })
+            .configuration(coverage_enabled())
+            .paths(setup.paths())
+            .call()
+    );
+
+    let mut expected_hyp_coverage = simple_project_hyp_coverage(&setup);
+    expected_hyp_coverage.mark_outdated(Utf8PathBuf::from("src/lib.rs"));
+
+    assert_eq!(0, coverage_rx.try_iter().count());
+    assert_eq!(&expected_hyp_coverage, handle_hyp_run.hyp_coverage());
+}
+
+#[test]
+pub fn when_no_hyp_executed_the_changed_lines_every_hyp_runs()
+{
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build();
+    let source = fs::read_to_string(setup.workspace_path().join("src").join("lib.rs")).unwrap();
+
+    // Only 'repeat' changed since the coverage was recorded, which no hyp executed
+    let mut hyp_coverage = simple_project_hyp_coverage(&setup);
+    hyp_coverage.record_source(
+        Utf8PathBuf::from("src/lib.rs"),
+        source.replace("str.repeat(times)", "str.repeat(times + 1)")
+    );
+
+    let mut run_hyps = MockRunHyps::new();
+    run_hyps.expect_run_hyp_set::<MockHypSessionBridge<RustBridge>>().never();
+    run_hyps
+        .expect_run_hyps::<MockHypSessionBridge<RustBridge>>()
+        .once()
+        .returning(|_, _| Ok(()));
+
+    HandleHypRunRequest::new()
+        .with_runner(run_hyps)
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_hyp_coverage(hyp_coverage)
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::Impacted {
+                    changed_files: vec![setup.workspace_path().join("src").join("lib.rs")]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+}
+
+#[test]
+pub fn a_hyp_added_since_the_coverage_was_recorded_runs()
+{
+    let (session_tx, session_rx) = crossbeam_channel::unbounded();
+
+    // The fixture is copied, as the hyp is added to its source
+    for file in ["Cargo.toml", "Cargo.lock", "src/lib.rs"]
+    {
+        copy_from_data_to_output(Utf8Path::new("simple_project").join(file)).unwrap();
+    }
+
+    let setup = TestDataSetup::builder(test_name!(), "simple_project")
+        .base_workspace_path(test_output_path())
+        .build()
+        .clean_output();
+
+    let lib_path = setup.workspace_path().join("src").join("lib.rs");
+    let source = fs::read_to_string(&lib_path).unwrap();
+
+    let mut hyp_coverage = HypCoverage::default();
+    hyp_coverage.record_source(Utf8PathBuf::from("src/lib.rs"), source.clone());
+    hyp_coverage.insert(
+        HypId::new("sample_project", "sample_project", "test::add_8_and_8_is_16"),
+        lcov::to_covdir([lib_record(&[(3, 1), (7, 0), (11, 0)])])
+    );
+
+    let added_hyp = r#"
+    #[test]
+    fn add_2_and_3_is_5() {
+        assert_eq!(add(2, 3), 5);
+    }
+}
+"#;
+    fs::write(
+        &lib_path,
+        source.trim_end().strip_suffix('}').unwrap().to_string() + added_hyp
+    )
+    .unwrap();
+
+    HandleHypRunRequest::new()
+        .with_hyp_session_bridge(session_tx)
+        .with_runner(HypRunner)
+        .with_hyp_coverage(hyp_coverage)
+        .call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::Impacted {
+                    changed_files: vec![lib_path]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+
+    let session = HypSession::from_events(session_rx.try_iter());
+    let added_hyp = HypId::new("sample_project", "sample_project", "test::add_2_and_3_is_5");
+
+    assert_matches!(session.hyps().get(added_hyp.chain()).unwrap().state(), HypState::Passed);
+}
+
+#[test]
+pub fn when_a_file_changes_again_after_its_impacted_run_every_hyp_runs()
+{
+    let setup = TestDataSetup::builder(test_name!(), "simple_project").build();
+
+    let mut run_hyps = MockRunHyps::new();
+    run_hyps
+        .expect_run_hyp_set::<MockHypSessionBridge<RustBridge>>()
+        .once()
+        .returning(|_, _, _| Ok(()));
+    run_hyps
+        .expect_run_hyps::<MockHypSessionBridge<RustBridge>>()
+        .once()
+        .returning(|_, _| Ok(()));
+
+    let mut handle_hyp_run = HandleHypRunRequest::new()
+        .with_runner(run_hyps)
+        .with_hyp_session_bridge(hyp_session_bridge::stub())
+        .with_hyp_coverage(simple_project_hyp_coverage(&setup));
+
+    for _ in 0 .. 2
+    {
+        handle_hyp_run.call(
+            HypRunRequest::stub()
+                .kind(HypRunRequestKind::Impacted {
+                    changed_files: vec![setup.workspace_path().join("src").join("lib.rs")]
+                })
+                .paths(setup.paths())
+                .call()
+        );
+    }
+}
+
+/// Coverage of 'simple_project' recorded before 'add' was changed to what it is now
+fn simple_project_hyp_coverage(setup: &TestDataSetup) -> HypCoverage
+{
+    let source = fs::read_to_string(setup.workspace_path().join("src").join("lib.rs")).unwrap();
+
+    let mut hyp_coverage = HypCoverage::default();
+    hyp_coverage.record_source(
+        Utf8PathBuf::from("src/lib.rs"),
+        source.replace("left + right", "left - right")
+    );
+    hyp_coverage.insert(add_hyp(), lcov::to_covdir([lib_record(&[(3, 1), (7, 0)])]));
+    hyp_coverage.insert(multiply_hyp(), lcov::to_covdir([lib_record(&[(3, 0), (7, 1)])]));
+
+    hyp_coverage
+}
+
+fn lib_record(lines: &[(u32, u64)]) -> LcovRecord
+{
+    LcovRecord {
+        source_file: Utf8PathBuf::from("src/lib.rs"),
+        lines: lines.iter().copied().collect(),
+        ..Default::default()
+    }
+}
+
+fn add_hyp() -> HypId
+{
+    HypId::new("simple_project", "simple_project", "add_works")
+}
+
+fn multiply_hyp() -> HypId
+{
+    HypId::new("simple_project", "simple_project", "multiply_works")
+}
+
+fn run_hyps_passing() -> MockRunHyps
+{
+    let mut run_hyps = MockRunHyps::new();
+    run_hyps
+        .expect_run_hyps::<crossbeam_channel::Sender<HypSessionEvent<RustBridge>>>()
+        .returning(|_, _| Ok(()));
+
+    run_hyps
+}
+
+fn compute_coverage_percent(coverage_percent: f64) -> MockComputeCoverage
+{
+    let mut compute_coverage = MockComputeCoverage::new();
+    compute_coverage.expect_clean_coverage_output().returning(|_| Ok(()));
+    compute_coverage.expect_compute_coverage().returning(move |_, _| {
+        Ok(CoverageStatus::Done(Box::new(CovdirJson {
+            coverage_percent,
+            ..Default::default()
+        })))
+    });
+    compute_coverage
+        .expect_compute_hyp_coverage()
+        .returning(|_, _| Ok(HypCoverage::default()));
+
+    compute_coverage
+}
+
+fn coverage_minimum(minimum_percent: f64) -> PassivateConfiguration
+{
+    PassivateConfiguration {
+        coverage_enabled: true,
+        coverage_thresholds: CoverageThresholds {
+            global: Some(minimum_percent),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn coverage_enabled() -> PassivateConfiguration
+{
+    PassivateConfiguration {
+        coverage_enabled: true,
+        ..Default::default()
+    }
+}
+
+fn stderr_output<'a>(output: impl Iterator<Item = &'a RustOutput>) -> impl Iterator<Item = &'a RustOutput>
+{
+    output.filter(|output| {
+        matches!(
+            output,
+            RustOutput::Console(ConsoleOutput {
+                kind: ConsoleOutputKind::StdErr,
+                ..
+            })
+        )
+    })
+}
@@ -4,17 +4,25 @@ use std::ops::{Deref, DerefMut};
 use passivate_id_chain_tree::id_chain::IdChain;
 use passivate_model_bridge::bridge::Bridge;
 use passivate_model_bridge::bridge_hyp::BridgeHyp;
+use passivate_model_bridge::bridge_output::BridgeOutput;
+use passivate_model_bridge::compiler_diagnostic::CompilerDiagnostic;
+use passivate_model_bridge::hyp_failure::HypFailure;
 use passivate_model_bridge::hyp_report::HypReport;
 use passivate_model_bridge::hyp_session_bridge::{
+    BuildFailedBridge,
     CancelRunBridge,
     CompleteRunBridge,
     RunErrorBridge,
     SendHypBridge,
     SendOutputBridge,
+    SendProgressBridge,
+    SendSummaryBridge,
     StartRunBridge
 };
 use passivate_model_bridge::hyp_session_event::{CompilationMessage, HypSessionEvent};
 use passivate_model_bridge::output_report::OutputReport;
+use passivate_model_bridge::run_extent::RunExtent;
+use passivate_model_bridge::run_progress::{RunProgress, RunSummary};
 use passivate_model_core::hyp_session::HypSession;
 
 #[derive(Default, Debug, PartialEq, Eq)]
@@ -181,6 +189,19 @@ impl Display for TestOutput
     }
 }
 
+impl BridgeOutput for TestOutput
+{
+    fn diagnostic(&self) -> Option<&CompilerDiagnostic>
+    {
+        None
+    }
+
+    fn failure(&self) -> Option<&HypFailure>
+    {
+        None
+    }
+}
+
 impl<T> From<T> for TestOutput
 where
     T: Into<String>
@@ -202,9 +223,9 @@ impl Bridge for TestSession
 
 impl StartRunBridge<TestSession> for TestSession
 {
-    fn start_run(&mut self)
+    fn start_run(&mut self, extent: RunExtent)
     {
-        self.0.update(HypSessionEvent::RunStarted);
+        self.0.update(HypSessionEvent::RunStarted(extent));
     }
 }
 
@@ -247,3 +268,27 @@ impl RunErrorBridge<TestSession> for TestSession
         self.0.update(HypSessionEvent::RunError(run_error));
     }
 }
+
+impl BuildFailedBridge<TestSession> for TestSession
+{
+    fn build_failed(&mut self, errors: Vec<CompilerDiagnostic>)
+    {
+        self.0.update(HypSessionEvent::BuildFailed(errors));
+    }
+}
+
+impl SendProgressBridge<TestSession> for TestSession
+{
+    fn send_progress(&mut self, progress: RunProgress)
+    {
+        self.0.update(HypSessionEvent::Progress(progress));
+    }
+}
+
+impl SendSummaryBridge<TestSession> for TestSession
+{
+    fn send_summary(&mut self, summary: RunSummary)
+    {
+        self.0.update(HypSessionEvent::Summary(summary));
+    }
+}
@@ -0,0 +1,7 @@
+pub fn add(left: u64, right: u64) -> u64 {
+    left + right
+}
+
+pub fn multiply(left: u64, right: u64) -> String {
+    left * right
+}
@@ -0,0 +1,8 @@
+pub fn add(left: u64, right: u64) -> u64 {
+    left + right
+}
+
+#[cfg(feature = "subtract")]
+pub fn subtract(left: u64, right: u64) -> u64 {
+    left - right
+}
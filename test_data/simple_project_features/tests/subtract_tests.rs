@@ -0,0 +1,9 @@
+#![cfg(feature = "subtract")]
+
+use sample_project::subtract;
+
+#[test]
+fn subtract_2_from_6_is_4() {
+    let result = subtract(6, 2);
+    assert_eq!(result, 4);
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use sample_project::add;
+
+#[test]
+fn add_2_and_2_is_4() {
+    let result = add(2, 2);
+    assert_eq!(result, 4);
+}
+
+// Fails every other attempt, so it passes when it is retried after failing
+#[test]
+fn add_2_and_4_is_6_every_other_time() {
+    let marker = Path::new(env!("CARGO_TARGET_TMPDIR")).join("failed_before");
+
+    if marker.exists() {
+        std::fs::remove_file(marker).unwrap();
+    } else {
+        std::fs::write(marker, "").unwrap();
+        panic!("failed this time");
+    }
+
+    let result = add(2, 4);
+    assert_eq!(result, 6);
+}
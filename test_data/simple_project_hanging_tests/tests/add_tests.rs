@@ -0,0 +1,20 @@
+use std::thread;
+use std::time::Duration;
+
+use sample_project::add;
+
+#[test]
+fn add_2_and_2_is_4() {
+    let result = add(2, 2);
+    assert_eq!(result, 4);
+}
+
+#[test]
+fn add_2_and_4_is_6_eventually() {
+    println!("waiting before adding");
+
+    thread::sleep(Duration::from_secs(60));
+
+    let result = add(2, 4);
+    assert_eq!(result, 6);
+}
@@ -0,0 +1,7 @@
+use sample_project::multiply;
+
+#[test]
+fn multiply_2_and_2_is_4() {
+    let result = multiply(2, 2);
+    assert_eq!(result, 4);
+}
\ No newline at end of file
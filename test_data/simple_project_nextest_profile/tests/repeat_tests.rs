@@ -0,0 +1,7 @@
+use sample_project::repeat;
+
+#[test]
+fn repeat_bla_four_times() {
+    let result = repeat("bla", 4);
+    assert_eq!(result, "blablablabla");
+}
\ No newline at end of file